@@ -0,0 +1,265 @@
+use crate::clients::cache::{CacheConfig, CacheTransport};
+use crate::clients::common::get_base_url;
+use crate::clients::config::ChiaConfig;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::retry::{RetryPolicy, RetryTransport};
+use crate::clients::ssl::{ChiaService, SslFiles};
+use crate::clients::transport::{ReqwestTransport, RpcTransport};
+use reqwest::{Client, ClientBuilder, Proxy};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Implemented by every RPC client so it can be created through [`RpcClientBuilder`].
+pub trait ServiceClient: Sized {
+    const SERVICE: ChiaService;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self;
+    fn transport(&self) -> &Arc<dyn RpcTransport>;
+}
+
+/// Adds the constructors and generic calls every client shares as inherent methods, so they can
+/// be used without importing [`ServiceClient`].
+macro_rules! client_methods {
+    ($client:ty) => {
+        impl $client {
+            pub fn try_new(
+                host: &str,
+                port: u32,
+                ssl_path: &str,
+            ) -> Result<Self, $crate::clients::error::ChiaRpcError> {
+                Self::builder()
+                    .host(host)
+                    .port(port)
+                    .ssl_path(ssl_path)
+                    .build()
+            }
+            /// Skips loading the private certificates, for use behind a TLS terminating proxy
+            /// with a publicly trusted certificate. Build with [`Self::builder`] and
+            /// `plain_http()` for a proxy that serves plain HTTP.
+            pub fn new_unauthenticated(
+                host: &str,
+                port: u32,
+            ) -> Result<Self, $crate::clients::error::ChiaRpcError> {
+                Self::builder()
+                    .host(host)
+                    .port(port)
+                    .unauthenticated()
+                    .build()
+            }
+            /// Connects with the host, port and certificates of the service found in `CHIA_ROOT`.
+            pub fn from_chia_root() -> Result<Self, $crate::clients::error::ChiaRpcError> {
+                Self::builder()
+                    .chia_config(&$crate::clients::config::ChiaConfig::load()?)
+                    .build()
+            }
+            pub fn builder() -> $crate::clients::builder::RpcClientBuilder<Self> {
+                $crate::clients::builder::RpcClientBuilder::new()
+            }
+            /// Calls any endpoint with the same TLS and error handling, returning the raw response.
+            pub async fn call_raw(
+                &self,
+                endpoint: &str,
+                data: serde_json::Value,
+            ) -> Result<serde_json::Value, $crate::clients::error::ChiaRpcError> {
+                $crate::clients::common::call_raw(self.rpc_transport(), endpoint, data).await
+            }
+            /// Calls any endpoint with a typed request and response.
+            pub async fn call<Req, Resp>(
+                &self,
+                endpoint: &str,
+                data: &Req,
+            ) -> Result<Resp, $crate::clients::error::ChiaRpcError>
+            where
+                Req: serde::Serialize + ?Sized,
+                Resp: serde::de::DeserializeOwned,
+            {
+                $crate::clients::common::call(self.rpc_transport(), endpoint, data).await
+            }
+            /// Sends a typed request, see [`crate::clients::requests`].
+            pub async fn request<R: $crate::clients::requests::RpcRequest>(
+                &self,
+                data: &R,
+            ) -> Result<R::Response, $crate::clients::error::ChiaRpcError> {
+                $crate::clients::common::request(self.rpc_transport(), data).await
+            }
+            fn rpc_transport(&self) -> &dyn $crate::clients::transport::RpcTransport {
+                <Self as $crate::clients::builder::ServiceClient>::transport(self).as_ref()
+            }
+        }
+    };
+}
+pub(crate) use client_methods;
+
+pub struct RpcClientBuilder<C: ServiceClient> {
+    host: String,
+    port: u32,
+    ssl_files: Option<SslFiles>,
+    unauthenticated: bool,
+    plain_http: bool,
+    base_url: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: Option<String>,
+    client: Option<Client>,
+    retry: Option<RetryPolicy>,
+    cache: Option<CacheConfig>,
+    _client: PhantomData<C>,
+}
+
+impl<C: ServiceClient> Default for RpcClientBuilder<C> {
+    fn default() -> Self {
+        RpcClientBuilder {
+            host: "localhost".to_string(),
+            port: C::SERVICE.default_rpc_port(),
+            ssl_files: None,
+            unauthenticated: false,
+            plain_http: false,
+            base_url: None,
+            connect_timeout: None,
+            timeout: Some(Duration::from_secs(300)),
+            proxy: None,
+            user_agent: None,
+            client: None,
+            retry: None,
+            cache: None,
+            _client: PhantomData,
+        }
+    }
+}
+
+impl<C: ServiceClient> RpcClientBuilder<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn host(mut self, host: &str) -> Self {
+        self.host = host.to_string();
+        self
+    }
+    pub fn port(mut self, port: u32) -> Self {
+        self.port = port;
+        self
+    }
+    /// Directory holding the Chia ssl folders, ie `~/.chia/mainnet/config/ssl`.
+    pub fn ssl_path(mut self, ssl_path: &str) -> Self {
+        self.ssl_files = Some(SslFiles::for_service(ssl_path, C::SERVICE));
+        self
+    }
+    /// Uses a certificate pair outside of the standard ssl layout.
+    pub fn ssl_files(mut self, ssl_files: SslFiles) -> Self {
+        self.ssl_files = Some(ssl_files);
+        self
+    }
+    /// Takes the host, port and certificates of the service from a Chia config.
+    pub fn chia_config(self, config: &ChiaConfig) -> Self {
+        self.host(config.self_hostname())
+            .port(config.rpc_port(C::SERVICE))
+            .ssl_files(config.ssl_files(C::SERVICE))
+    }
+    /// Allows building without certificates, for proxies or the simulator. The server is then
+    /// verified against the public webpki roots, so the private Chia CA is not trusted.
+    pub fn unauthenticated(mut self) -> Self {
+        self.unauthenticated = true;
+        self
+    }
+    /// Talks `http://` instead of `https://`, implies [`Self::unauthenticated`].
+    pub fn plain_http(mut self) -> Self {
+        self.plain_http = true;
+        self.unauthenticated = true;
+        self
+    }
+    /// Overrides the `https://{host}:{port}/` prefix, ie `https://proxy.local/chia/full_node/`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+    /// Total time allowed per request, defaults to 300 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+    /// Uses an existing client as is, the TLS, timeout, proxy and user agent options are ignored.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+    /// Retries failed read calls, see [`RetryPolicy`].
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+    /// Caches immutable chain data in front of the node, see [`CacheTransport`].
+    pub fn cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+    pub fn build(self) -> Result<C, ChiaRpcError> {
+        let base_url = match &self.base_url {
+            Some(base_url) if base_url.ends_with('/') => base_url.clone(),
+            Some(base_url) => format!("{}/", base_url),
+            None => get_base_url(
+                if self.plain_http { "http" } else { "https" },
+                &self.host,
+                self.port,
+            ),
+        };
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = ClientBuilder::new();
+                match (&self.ssl_files, self.unauthenticated) {
+                    (Some(ssl_files), _) => {
+                        builder = builder.use_preconfigured_tls(ssl_files.client_config()?);
+                    }
+                    // reqwest falls back to the webpki roots.
+                    (None, true) => {}
+                    (None, false) => {
+                        return Err(ChiaRpcError::Config(
+                            "No ssl_path provided, call unauthenticated() to connect without certificates".to_string(),
+                        ));
+                    }
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder
+                    .build()
+                    .map_err(|e| ChiaRpcError::Config(format!("Failed to build client: {:?}", e)))?
+            }
+        };
+        let mut transport: Arc<dyn RpcTransport> =
+            Arc::new(ReqwestTransport::new(client, base_url));
+        if let Some(policy) = self.retry {
+            transport = Arc::new(RetryTransport::new(transport, policy));
+        }
+        if let Some(config) = self.cache {
+            transport = Arc::new(CacheTransport::new(transport, config));
+        }
+        Ok(C::from_transport(transport))
+    }
+}
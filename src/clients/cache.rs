@@ -0,0 +1,431 @@
+use crate::clients::error::ChiaRpcError;
+use crate::clients::transport::RpcTransport;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Calls whose response never changes for the same key field.
+const IMMUTABLE: [(&str, &str); 3] = [
+    ("get_block", "header_hash"),
+    ("get_block_record", "header_hash"),
+    ("get_additions_and_removals", "header_hash"),
+];
+
+/// Calls keyed by height and an optional id field, only cached once the height is buried deep
+/// enough. A coin spent near the peak can be reorged out and spent again with another solution.
+const BY_HEIGHT: [(&str, Option<&str>); 2] = [
+    ("get_block_record_by_height", None),
+    ("get_puzzle_and_solution", Some("coin_id")),
+];
+
+/// Settings of [`CacheTransport`].
+///
+/// Defaults to at most 1 000 responses and 256 MiB of JSON in memory, no disk store, a reorg
+/// depth of 32 blocks and refreshing the peak height at most every 30 seconds.
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    capacity: usize,
+    max_bytes: usize,
+    dir: Option<PathBuf>,
+    reorg_depth: u32,
+    peak_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            capacity: 1_000,
+            max_bytes: 256 * 1024 * 1024,
+            dir: None,
+            reorg_depth: 32,
+            peak_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Number of responses kept in memory, the least recently used are dropped first.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+    /// Total size of the responses kept in memory, measured as serialized JSON. Full blocks can
+    /// be several megabytes each.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+    /// Also keeps every response as a file under `dir`, so it survives restarts.
+    pub fn disk<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+    /// Height lookups are only cached at least this many blocks below the peak.
+    pub fn reorg_depth(mut self, reorg_depth: u32) -> Self {
+        self.reorg_depth = reorg_depth;
+        self
+    }
+    /// How long a peak height from `get_blockchain_state` is trusted before asking again.
+    pub fn peak_ttl(mut self, peak_ttl: Duration) -> Self {
+        self.peak_ttl = peak_ttl;
+        self
+    }
+}
+
+#[derive(Default)]
+struct Lru {
+    /// Value, size in bytes and last use of every key.
+    entries: HashMap<String, (Value, usize, u64)>,
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    bytes: usize,
+}
+
+impl Lru {
+    fn get(&mut self, key: &str) -> Option<Value> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (value, _, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        self.order.insert(tick, key.to_string());
+        *used = tick;
+        Some(value.clone())
+    }
+    fn insert(&mut self, key: String, value: Value, size: usize, config: &CacheConfig) {
+        if size > config.max_bytes {
+            return;
+        }
+        self.tick += 1;
+        let entry = (value, size, self.tick);
+        if let Some((_, old_size, used)) = self.entries.insert(key.clone(), entry) {
+            self.order.remove(&used);
+            self.bytes -= old_size;
+        }
+        self.bytes += size;
+        self.order.insert(self.tick, key);
+        while self.entries.len() > config.capacity || self.bytes > config.max_bytes {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((_, size, _)) = self.entries.remove(&oldest) {
+                self.bytes -= size;
+            }
+        }
+    }
+}
+
+/// Serves immutable chain data from a cache in front of `inner`.
+///
+/// Blocks and block records by header hash and additions and removals by header hash are cached
+/// forever. `get_block_record_by_height` and `get_puzzle_and_solution`, keyed by coin id and
+/// height, are only cached for heights at least the reorg depth below the peak, which is read
+/// from `get_blockchain_state`.
+/// Failed responses are never cached, and a disk store that cannot be written is skipped.
+pub struct CacheTransport {
+    inner: Arc<dyn RpcTransport>,
+    config: CacheConfig,
+    memory: Mutex<Lru>,
+    peak: Mutex<Option<(u32, Instant)>>,
+}
+
+impl CacheTransport {
+    pub fn new(inner: Arc<dyn RpcTransport>, config: CacheConfig) -> Self {
+        CacheTransport {
+            inner,
+            config,
+            memory: Mutex::new(Lru::default()),
+            peak: Mutex::new(None),
+        }
+    }
+    /// Number of responses in memory.
+    pub fn len(&self) -> usize {
+        self.memory.lock().unwrap().entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Empties the memory cache, the disk store is left as is.
+    pub fn clear(&self) {
+        *self.memory.lock().unwrap() = Lru::default();
+    }
+    async fn cache_key(&self, endpoint: &str, body: &Value) -> Option<String> {
+        if let Some((_, field)) = IMMUTABLE.iter().find(|(e, _)| *e == endpoint) {
+            return body[*field]
+                .as_str()
+                .map(|key| format!("{}/{}", endpoint, normalize_hash(key)));
+        }
+        let (_, field) = BY_HEIGHT.iter().find(|(e, _)| *e == endpoint)?;
+        let height = body["height"].as_u64()?;
+        let key = match field {
+            Some(field) => {
+                let id = normalize_hash(body[*field].as_str()?);
+                format!("{}/{}/{}", endpoint, id, height)
+            }
+            None => format!("{}/{}", endpoint, height),
+        };
+        let peak = self.peak_height().await? as u64;
+        (height + self.config.reorg_depth as u64 <= peak).then_some(key)
+    }
+    async fn peak_height(&self) -> Option<u32> {
+        if let Some((peak, at)) = *self.peak.lock().unwrap() {
+            if at.elapsed() < self.config.peak_ttl {
+                return Some(peak);
+            }
+        }
+        let state = self
+            .inner
+            .send("get_blockchain_state", &json!({}))
+            .await
+            .ok()?;
+        self.observe_peak(&state)
+    }
+    fn observe_peak(&self, state: &Value) -> Option<u32> {
+        let peak = state["blockchain_state"]["peak"]["height"].as_u64()? as u32;
+        *self.peak.lock().unwrap() = Some((peak, Instant::now()));
+        Some(peak)
+    }
+    fn disk_path(&self, key: &str) -> Option<PathBuf> {
+        let dir = self.config.dir.as_ref()?;
+        let safe = key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/');
+        safe.then(|| dir.join(format!("{}.json", key)))
+    }
+    async fn load(&self, key: &str) -> Option<Value> {
+        if let Some(value) = self.memory.lock().unwrap().get(key) {
+            return Some(value);
+        }
+        let path = self.disk_path(key)?;
+        let contents = tokio::task::spawn_blocking(move || fs::read_to_string(path))
+            .await
+            .ok()?
+            .ok()?;
+        let value: Value = serde_json::from_str(&contents).ok()?;
+        self.memory.lock().unwrap().insert(
+            key.to_string(),
+            value.clone(),
+            contents.len(),
+            &self.config,
+        );
+        Some(value)
+    }
+    async fn store(&self, key: &str, value: &Value) {
+        let contents = value.to_string();
+        self.memory.lock().unwrap().insert(
+            key.to_string(),
+            value.clone(),
+            contents.len(),
+            &self.config,
+        );
+        if let Some(path) = self.disk_path(key) {
+            let _ = tokio::task::spawn_blocking(move || write_atomically(&path, &contents)).await;
+        }
+    }
+}
+
+/// The nodes accept hashes with or without `0x` and in either case, they name the same record.
+fn normalize_hash(hash: &str) -> String {
+    let hash = hash
+        .strip_prefix("0x")
+        .or_else(|| hash.strip_prefix("0X"))
+        .unwrap_or(hash);
+    hash.to_ascii_lowercase()
+}
+
+/// Writes through a temporary file renamed over `path`, so a crash or a concurrent reader never
+/// sees a truncated response.
+fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let write = WRITES.fetch_add(1, Ordering::Relaxed);
+    let temp = path.with_extension(format!("{}.{}.tmp", std::process::id(), write));
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+#[async_trait]
+impl RpcTransport for CacheTransport {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        let key = self.cache_key(endpoint, body).await;
+        if let Some(key) = key.as_deref() {
+            if let Some(value) = self.load(key).await {
+                return Ok(value);
+            }
+        }
+        let value = self.inner.send(endpoint, body).await?;
+        if endpoint == "get_blockchain_state" {
+            self.observe_peak(&value);
+        }
+        if let Some(key) = key {
+            if value["success"] == Value::Bool(true) {
+                self.store(&key, &value).await;
+            }
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::transport::MockTransport;
+    use tempfile::TempDir;
+
+    fn hash() -> String {
+        format!("0x{}", "ab".repeat(32))
+    }
+
+    fn block() -> Value {
+        json!({ "success": true, "block": { "header_hash": hash() } })
+    }
+
+    fn node(peak: u32) -> Arc<MockTransport> {
+        let node = Arc::new(MockTransport::new());
+        node.respond(
+            "get_blockchain_state",
+            json!({ "success": true, "blockchain_state": { "peak": { "height": peak } } }),
+        )
+        .respond("get_block", block())
+        .respond(
+            "get_block_record_by_height",
+            json!({ "success": true, "block_record": {} }),
+        );
+        node
+    }
+
+    fn cache(node: &Arc<MockTransport>, config: CacheConfig) -> CacheTransport {
+        CacheTransport::new(node.clone(), config)
+    }
+
+    fn insert(lru: &mut Lru, key: &str, size: usize, config: &CacheConfig) {
+        lru.insert(key.to_string(), json!(key), size, config);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used() {
+        let config = CacheConfig::new().capacity(2);
+        let mut lru = Lru::default();
+        insert(&mut lru, "a", 1, &config);
+        insert(&mut lru, "b", 1, &config);
+        assert_eq!(lru.get("a"), Some(json!("a")));
+        insert(&mut lru, "c", 1, &config);
+        assert_eq!(lru.get("b"), None);
+        assert_eq!(lru.get("a"), Some(json!("a")));
+        assert_eq!(lru.get("c"), Some(json!("c")));
+        assert_eq!(lru.order.len(), 2);
+    }
+
+    #[test]
+    fn evicts_to_stay_under_max_bytes() {
+        let config = CacheConfig::new().max_bytes(10);
+        let mut lru = Lru::default();
+        insert(&mut lru, "a", 4, &config);
+        insert(&mut lru, "b", 4, &config);
+        insert(&mut lru, "c", 4, &config);
+        assert_eq!(lru.get("a"), None);
+        assert_eq!(lru.bytes, 8);
+        insert(&mut lru, "huge", 11, &config);
+        assert_eq!(lru.get("huge"), None);
+        assert_eq!(lru.entries.len(), 2);
+    }
+
+    #[test]
+    fn replacing_a_key_updates_its_size() {
+        let config = CacheConfig::new();
+        let mut lru = Lru::default();
+        insert(&mut lru, "a", 4, &config);
+        insert(&mut lru, "a", 6, &config);
+        assert_eq!(lru.bytes, 6);
+        assert_eq!(lru.entries.len(), 1);
+        assert_eq!(lru.order.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn blocks_are_served_from_the_cache() {
+        let node = node(100);
+        let cache = cache(&node, CacheConfig::new());
+        let first = cache
+            .send("get_block", &json!({ "header_hash": hash() }))
+            .await
+            .unwrap();
+        let upper = format!("0x{}", "AB".repeat(32));
+        let bare = "ab".repeat(32);
+        for header_hash in [hash(), upper, bare] {
+            let body = json!({ "header_hash": header_hash });
+            assert_eq!(cache.send("get_block", &body).await.unwrap(), first);
+        }
+        assert_eq!(node.requests_to("get_block").len(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn heights_are_cached_once_below_the_reorg_depth() {
+        let node = node(100);
+        let cache = cache(&node, CacheConfig::new().reorg_depth(32));
+        for height in [69, 69, 68, 68] {
+            let body = json!({ "height": height });
+            cache
+                .send("get_block_record_by_height", &body)
+                .await
+                .unwrap();
+        }
+        let heights: Vec<Value> = node
+            .requests_to("get_block_record_by_height")
+            .into_iter()
+            .map(|body| body["height"].clone())
+            .collect();
+        assert_eq!(heights, [json!(69), json!(69), json!(68)]);
+        assert_eq!(node.requests_to("get_blockchain_state").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failures_are_not_cached() {
+        let node = Arc::new(MockTransport::new());
+        let cache = cache(&node, CacheConfig::new());
+        let body = json!({ "header_hash": hash() });
+        node.respond("get_block", json!({ "success": false, "error": "busy" }));
+        cache.send("get_block", &body).await.unwrap();
+        node.fail_once("get_block", ChiaRpcError::Unreachable(String::new()))
+            .respond("get_block", block());
+        assert!(cache.send("get_block", &body).await.is_err());
+        assert!(cache.is_empty());
+        cache.send("get_block", &body).await.unwrap();
+        cache.send("get_block", &body).await.unwrap();
+        assert_eq!(node.requests_to("get_block").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn disk_store_survives_a_new_transport() {
+        let dir = TempDir::new().unwrap();
+        let body = json!({ "header_hash": hash() });
+        let first = node(100);
+        let stored = cache(&first, CacheConfig::new().disk(dir.path()))
+            .send("get_block", &body)
+            .await
+            .unwrap();
+        let second = Arc::new(MockTransport::new());
+        let loaded = cache(&second, CacheConfig::new().disk(dir.path()))
+            .send("get_block", &body)
+            .await
+            .unwrap();
+        assert_eq!(loaded, stored);
+        assert!(second.requests().is_empty());
+        let files: Vec<_> = fs::read_dir(dir.path().join("get_block"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(files, [format!("{}.json", "ab".repeat(32))]);
+    }
+}
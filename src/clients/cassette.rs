@@ -0,0 +1,436 @@
+use crate::clients::error::ChiaRpcError;
+use crate::clients::transport::RpcTransport;
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Interaction {
+    pub endpoint: String,
+    pub request: Value,
+    /// `null` when the call failed.
+    pub response: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RecordedError>,
+}
+
+/// A failed call as stored in a cassette.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedError {
+    /// The node answered with a non-200 status code.
+    HttpStatus {
+        status: u16,
+        body: String,
+    },
+    /// The node answered with a body that is not JSON.
+    Decode {
+        body: String,
+    },
+    Timeout,
+    /// The node could not be reached, replayed as [`ChiaRpcError::Unreachable`].
+    Transport {
+        message: String,
+    },
+}
+
+impl RecordedError {
+    fn record(error: &ChiaRpcError) -> Self {
+        match error {
+            ChiaRpcError::HttpStatus { status, body, .. } => RecordedError::HttpStatus {
+                status: status.as_u16(),
+                body: body.clone(),
+            },
+            ChiaRpcError::Decode { body, .. } => RecordedError::Decode { body: body.clone() },
+            ChiaRpcError::Unreachable(message) => RecordedError::Transport {
+                message: message.clone(),
+            },
+            e if e.is_timeout() => RecordedError::Timeout,
+            e => RecordedError::Transport {
+                message: e.to_string(),
+            },
+        }
+    }
+    fn replay(&self, endpoint: &str) -> ChiaRpcError {
+        match self {
+            RecordedError::HttpStatus { status, body } => ChiaRpcError::HttpStatus {
+                url: endpoint.to_string(),
+                status: StatusCode::from_u16(*status).unwrap_or(StatusCode::BAD_GATEWAY),
+                body: body.clone(),
+            },
+            RecordedError::Decode { body } => match serde_json::from_str::<Value>(body) {
+                Err(e) => ChiaRpcError::Decode {
+                    endpoint: endpoint.to_string(),
+                    body: body.clone(),
+                    path: String::new(),
+                    source: e,
+                },
+                Ok(_) => ChiaRpcError::UnexpectedResponse {
+                    endpoint: endpoint.to_string(),
+                    message: format!("Recorded as undecodable: {}", body),
+                },
+            },
+            RecordedError::Timeout => ChiaRpcError::Timeout(endpoint.to_string()),
+            RecordedError::Transport { message } => ChiaRpcError::Unreachable(message.clone()),
+        }
+    }
+}
+
+/// Request/response pairs recorded from a node, stored as a JSON file.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ChiaRpcError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| {
+            ChiaRpcError::Config(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&contents)
+            .map_err(|e| ChiaRpcError::Config(format!("Failed to parse {}: {}", path.display(), e)))
+    }
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ChiaRpcError> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| ChiaRpcError::Config(format!("Failed to serialize cassette: {}", e)))?;
+        fs::write(path, contents)
+            .map_err(|e| ChiaRpcError::Config(format!("Failed to write {}: {}", path.display(), e)))
+    }
+}
+
+/// Rewrites an interaction before it is written, ie to strip keys or addresses.
+pub type Redactor = Box<dyn Fn(&mut Interaction) + Send + Sync>;
+
+/// Forwards every call to `inner` and keeps the exchanges, failed calls included, in memory.
+///
+/// They are written to the cassette file by [`Self::flush`], and when the transport is dropped
+/// if anything was recorded since. A cassette that cannot be written never fails the call itself,
+/// only `flush` reports it.
+pub struct RecordingTransport {
+    inner: Arc<dyn RpcTransport>,
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    /// Number of interactions already written to `path`.
+    saved: AtomicUsize,
+    redactors: Vec<Redactor>,
+}
+
+impl RecordingTransport {
+    pub fn new<P: AsRef<Path>>(inner: Arc<dyn RpcTransport>, path: P) -> Self {
+        RecordingTransport {
+            inner,
+            path: path.as_ref().to_path_buf(),
+            cassette: Mutex::new(Cassette::default()),
+            saved: AtomicUsize::new(0),
+            redactors: vec![],
+        }
+    }
+    pub fn with_redactor<F>(mut self, redactor: F) -> Self
+    where
+        F: Fn(&mut Interaction) + Send + Sync + 'static,
+    {
+        self.redactors.push(Box::new(redactor));
+        self
+    }
+    /// Replaces the value of every object key named in `fields`, at any depth, with `"REDACTED"`.
+    pub fn redact_fields(self, fields: &[&str]) -> Self {
+        let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+        self.with_redactor(move |interaction| {
+            redact_value(&mut interaction.request, &fields);
+            redact_value(&mut interaction.response, &fields);
+        })
+    }
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+    /// Writes the interactions recorded so far to the cassette file.
+    pub async fn flush(&self) -> Result<(), ChiaRpcError> {
+        let cassette = self.cassette();
+        let count = cassette.interactions.len();
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || cassette.save(path))
+            .await
+            .map_err(|e| ChiaRpcError::Config(format!("Failed to write cassette: {}", e)))??;
+        self.saved.fetch_max(count, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+impl Drop for RecordingTransport {
+    fn drop(&mut self) {
+        let cassette = self
+            .cassette
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if cassette.interactions.len() > *self.saved.get_mut() {
+            let _ = cassette.save(&self.path);
+        }
+    }
+}
+
+#[async_trait]
+impl RpcTransport for RecordingTransport {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        let result = self.inner.send(endpoint, body).await;
+        let mut interaction = Interaction {
+            endpoint: endpoint.to_string(),
+            request: body.clone(),
+            response: result.as_ref().cloned().unwrap_or(Value::Null),
+            error: result.as_ref().err().map(RecordedError::record),
+        };
+        for redactor in &self.redactors {
+            redactor(&mut interaction);
+        }
+        self.cassette.lock().unwrap().interactions.push(interaction);
+        result
+    }
+}
+
+fn redact_value(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if fields.contains(key) {
+                    *value = Value::String("REDACTED".to_string());
+                } else {
+                    redact_value(value, fields);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                redact_value(value, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Serves the responses of a cassette offline.
+///
+/// Interactions are matched on endpoint and request body and handed out in recorded order, once
+/// they run out the last match is repeated. Recorded failures are returned as errors again.
+pub struct ReplayTransport {
+    interactions: Vec<Interaction>,
+    used: Mutex<Vec<bool>>,
+    match_body: bool,
+}
+
+impl ReplayTransport {
+    pub fn new(cassette: Cassette) -> Self {
+        ReplayTransport {
+            used: Mutex::new(vec![false; cassette.interactions.len()]),
+            interactions: cassette.interactions,
+            match_body: true,
+        }
+    }
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ChiaRpcError> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+    /// Matches on endpoint only, for cassettes whose requests were redacted.
+    pub fn ignore_request_body(mut self) -> Self {
+        self.match_body = false;
+        self
+    }
+}
+
+#[async_trait]
+impl RpcTransport for ReplayTransport {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        let mut used = self.used.lock().unwrap();
+        let matches: Vec<usize> = self
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.endpoint == endpoint && (!self.match_body || &i.request == body))
+            .map(|(index, _)| index)
+            .collect();
+        let index = matches
+            .iter()
+            .find(|index| !used[**index])
+            .or_else(|| matches.last())
+            .copied()
+            .ok_or_else(|| ChiaRpcError::HttpStatus {
+                url: endpoint.to_string(),
+                status: StatusCode::NOT_FOUND,
+                body: format!(
+                    "No recorded interaction for {} with body {}",
+                    endpoint, body
+                ),
+            })?;
+        used[index] = true;
+        match &self.interactions[index].error {
+            Some(error) => Err(error.replay(endpoint)),
+            None => Ok(self.interactions[index].response.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::transport::MockTransport;
+    use serde_json::json;
+
+    fn mock() -> Arc<MockTransport> {
+        let mock = Arc::new(MockTransport::new());
+        mock.respond(
+            "get_blockchain_state",
+            json!({ "success": true, "peak": 10 }),
+        );
+        mock
+    }
+
+    #[tokio::test]
+    async fn records_and_replays_responses_and_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let inner = mock();
+        inner.fail_once(
+            "get_blockchain_state",
+            ChiaRpcError::HttpStatus {
+                url: "get_blockchain_state".to_string(),
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                body: "boom".to_string(),
+            },
+        );
+        inner.fail_once(
+            "get_network_info",
+            ChiaRpcError::Timeout("get_network_info".to_string()),
+        );
+        let recorder = RecordingTransport::new(inner, &path);
+        let body = json!({});
+        assert!(recorder.send("get_blockchain_state", &body).await.is_err());
+        assert!(recorder.send("get_blockchain_state", &body).await.is_ok());
+        assert!(recorder.send("get_network_info", &body).await.is_err());
+        recorder.flush().await.unwrap();
+
+        let cassette = Cassette::load(&path).unwrap();
+        assert_eq!(cassette, recorder.cassette());
+        assert_eq!(
+            cassette.interactions[0].error,
+            Some(RecordedError::HttpStatus {
+                status: 500,
+                body: "boom".to_string()
+            })
+        );
+        assert_eq!(cassette.interactions[0].response, Value::Null);
+        assert_eq!(cassette.interactions[2].error, Some(RecordedError::Timeout));
+
+        let replay = ReplayTransport::load(&path).unwrap();
+        match replay.send("get_blockchain_state", &body).await {
+            Err(ChiaRpcError::HttpStatus { status, body, .. }) => {
+                assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+                assert_eq!(body, "boom");
+            }
+            other => panic!("Expected the recorded 500, got {:?}", other),
+        }
+        assert_eq!(
+            replay.send("get_blockchain_state", &body).await.unwrap(),
+            json!({ "success": true, "peak": 10 })
+        );
+        assert_eq!(
+            replay.send("get_blockchain_state", &body).await.unwrap(),
+            json!({ "success": true, "peak": 10 })
+        );
+        assert!(replay
+            .send("get_network_info", &body)
+            .await
+            .unwrap_err()
+            .is_timeout());
+        assert!(replay
+            .send("get_network_info", &json!({ "x": 1 }))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn unwritable_cassette_does_not_fail_the_call() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingTransport::new(mock(), dir.path().join("missing/cassette.json"));
+        assert!(recorder
+            .send("get_blockchain_state", &json!({}))
+            .await
+            .is_ok());
+        assert!(matches!(
+            recorder.flush().await,
+            Err(ChiaRpcError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn drop_writes_unsaved_interactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let recorder = RecordingTransport::new(mock(), &path);
+        recorder
+            .send("get_blockchain_state", &json!({}))
+            .await
+            .unwrap();
+        assert!(!path.exists());
+        drop(recorder);
+        assert_eq!(Cassette::load(&path).unwrap().interactions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn redacts_nested_fields() {
+        let inner = Arc::new(MockTransport::new());
+        inner.respond(
+            "log_in",
+            json!({ "success": true, "keys": [{ "fingerprint": 1, "label": "a" }] }),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingTransport::new(inner, dir.path().join("cassette.json"))
+            .redact_fields(&["fingerprint"]);
+        let response = recorder
+            .send("log_in", &json!({ "fingerprint": 1 }))
+            .await
+            .unwrap();
+        assert_eq!(response["keys"][0]["fingerprint"], json!(1));
+        let cassette = recorder.cassette();
+        assert_eq!(
+            cassette.interactions[0].request,
+            json!({ "fingerprint": "REDACTED" })
+        );
+        assert_eq!(
+            cassette.interactions[0].response["keys"],
+            json!([{ "fingerprint": "REDACTED", "label": "a" }])
+        );
+
+        let replay = ReplayTransport::new(cassette).ignore_request_body();
+        assert_eq!(
+            replay
+                .send("log_in", &json!({ "fingerprint": 2 }))
+                .await
+                .unwrap()["keys"][0]["label"],
+            json!("a")
+        );
+    }
+
+    #[tokio::test]
+    async fn transport_failures_replay_as_unreachable() {
+        let cassette = Cassette {
+            interactions: vec![Interaction {
+                endpoint: "get_network_info".to_string(),
+                request: json!({}),
+                response: Value::Null,
+                error: Some(RecordedError::Transport {
+                    message: "connection refused".to_string(),
+                }),
+            }],
+        };
+        let error = ReplayTransport::new(cassette)
+            .send("get_network_info", &json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ChiaRpcError::Unreachable(_)));
+        assert!(error.is_retryable());
+    }
+}
@@ -1,69 +1,106 @@
-use reqwest::{Certificate, Client, ClientBuilder, Identity};
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::RpcRequest;
+use crate::clients::transport::RpcTransport;
+use reqwest::Client;
 use serde::de::DeserializeOwned;
-use serde_json::{Map, Value};
-use std::fs::File;
-use std::io::{Error, ErrorKind, Read};
-use std::time::Duration;
+use serde::Serialize;
+use serde_json::Value;
 
-pub fn get_url(host: &str, port: u32, request_uri: &str) -> String {
+pub fn get_base_url(scheme: &str, host: &str, port: u32) -> String {
     format!(
-        "https://{host}:{port}/{request_uri}",
+        "{scheme}://{host}:{port}/",
+        scheme = scheme,
         host = host,
-        port = port,
+        port = port
+    )
+}
+
+pub fn get_url(base_url: &str, request_uri: &str) -> String {
+    format!(
+        "{base_url}{request_uri}",
+        base_url = base_url,
         request_uri = request_uri
     )
 }
 
-pub fn get_client(ssl_path: &str) -> Result<Client, Error> {
-    let mut cert_buf = Vec::new();
-    File::open(format!("{}/{}", ssl_path, "/daemon/private_daemon.crt"))?
-        .read_to_end(&mut cert_buf)?;
-    let cert = Certificate::from_pem(&cert_buf).map_err(|e| {
-        Error::new(
-            ErrorKind::InvalidData,
-            format!("Failed to parse Certificate file: {:?}", e),
-        )
-    })?;
-    let mut key_buf = Vec::new();
-    File::open(format!("{}/{}", ssl_path, "/daemon/private_daemon.key"))?
-        .read_to_end(&mut key_buf)?;
-    let id = Identity::from_pem(&key_buf).map_err(|e| {
-        Error::new(
-            ErrorKind::InvalidData,
-            format!("Failed to parse Identity file: {:?}", e),
-        )
+pub async fn post_json(client: &Client, url: &str, data: &Value) -> Result<Value, ChiaRpcError> {
+    let resp = client.post(url).json(data).send().await?;
+    let status = resp.status();
+    let body = resp.text().await?;
+    if status != reqwest::StatusCode::OK {
+        return Err(ChiaRpcError::HttpStatus {
+            url: url.to_string(),
+            status,
+            body,
+        });
+    }
+    serde_json::from_str(&body).map_err(|e| ChiaRpcError::Decode {
+        endpoint: url.to_string(),
+        body,
+        path: String::new(),
+        source: e,
+    })
+}
+
+pub async fn call_raw(
+    transport: &dyn RpcTransport,
+    endpoint: &str,
+    data: Value,
+) -> Result<Value, ChiaRpcError> {
+    let value = transport.send(endpoint, &data).await?;
+    check_success(endpoint, &value)?;
+    Ok(value)
+}
+
+pub async fn call<Req, Resp>(
+    transport: &dyn RpcTransport,
+    endpoint: &str,
+    data: &Req,
+) -> Result<Resp, ChiaRpcError>
+where
+    Req: Serialize + ?Sized,
+    Resp: DeserializeOwned,
+{
+    let data = serde_json::to_value(data).map_err(|e| {
+        ChiaRpcError::InvalidInput(format!(
+            "Failed to serialize request to {}: {}",
+            endpoint, e
+        ))
     })?;
-    ClientBuilder::new()
-        .add_root_certificate(cert)
-        .identity(id)
-        .timeout(Duration::from_secs(300))
-        .build()
-        .map_err(|e| Error::new(ErrorKind::Other, format!("{:?}", e)))
+    decode(endpoint, transport.send(endpoint, &data).await?)
 }
 
-pub async fn post<T>(client: &Client, url: &str, data: &Map<String, Value>) -> Result<T, Error>
+pub async fn request<R: RpcRequest>(
+    transport: &dyn RpcTransport,
+    request: &R,
+) -> Result<R::Response, ChiaRpcError> {
+    call(transport, R::ENDPOINT, request).await
+}
+
+pub fn decode<T>(endpoint: &str, value: Value) -> Result<T, ChiaRpcError>
 where
     T: DeserializeOwned,
 {
-    match client.post(url).json(data).send().await {
-        Ok(resp) => match resp.status() {
-            reqwest::StatusCode::OK => {
-                let body = resp
-                    .text()
-                    .await
-                    .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
-                serde_json::from_str(body.as_str()).map_err(|e| {
-                    Error::new(
-                        ErrorKind::InvalidData,
-                        format!("Failed to Parse Json {},\r\n {}", body, e),
-                    )
-                })
-            }
-            _ => Err(Error::new(
-                ErrorKind::InvalidData,
-                format!("Bad Status Code: {:?}, for URL {:?}", resp.status(), url),
-            )),
-        },
-        Err(err) => Err(Error::new(ErrorKind::InvalidData, format!("{:?}", err))),
+    check_success(endpoint, &value)?;
+    serde_path_to_error::deserialize(&value).map_err(|e| ChiaRpcError::Decode {
+        endpoint: endpoint.to_string(),
+        path: e.path().to_string(),
+        source: e.into_inner(),
+        body: value.to_string(),
+    })
+}
+
+/// Chia reports failures as `{"success": false, "error": "..."}` with a 200 status code.
+pub fn check_success(endpoint: &str, value: &Value) -> Result<(), ChiaRpcError> {
+    match value.get("success") {
+        Some(Value::Bool(false)) => Err(ChiaRpcError::Rpc {
+            endpoint: endpoint.to_string(),
+            message: match value.get("error") {
+                Some(Value::String(msg)) => msg.clone(),
+                Some(other) => other.to_string(),
+                None => "Unknown Error".to_string(),
+            },
+        }),
+        _ => Ok(()),
     }
 }
@@ -0,0 +1,212 @@
+use crate::clients::error::ChiaRpcError;
+use crate::clients::ssl::{ChiaService, SslFiles};
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize, Default)]
+struct RawSsl {
+    private_crt: Option<String>,
+    private_key: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct RawCa {
+    crt: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct RawService {
+    rpc_port: Option<u32>,
+    ssl: Option<RawSsl>,
+}
+
+/// The crawler is configured under `seeder.crawler`.
+#[derive(Deserialize, Default)]
+struct RawSeeder {
+    crawler: Option<RawService>,
+}
+
+#[derive(Deserialize, Default)]
+struct RawConfig {
+    self_hostname: Option<String>,
+    daemon_port: Option<u32>,
+    daemon_ssl: Option<RawSsl>,
+    private_ssl_ca: Option<RawCa>,
+    full_node: Option<RawService>,
+    wallet: Option<RawService>,
+    farmer: Option<RawService>,
+    harvester: Option<RawService>,
+    data_layer: Option<RawService>,
+    timelord: Option<RawService>,
+    seeder: Option<RawSeeder>,
+}
+
+/// Endpoints and certificates read from `{CHIA_ROOT}/config/config.yaml`.
+pub struct ChiaConfig {
+    root: PathBuf,
+    raw: RawConfig,
+}
+
+impl ChiaConfig {
+    /// Loads the config of the install pointed to by `CHIA_ROOT`, see [`chia_root`].
+    pub fn load() -> Result<Self, ChiaRpcError> {
+        Self::load_from(chia_root()?)
+    }
+    pub fn load_from<P: AsRef<Path>>(root: P) -> Result<Self, ChiaRpcError> {
+        let root = root.as_ref().to_path_buf();
+        let path = root.join("config").join("config.yaml");
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            ChiaRpcError::Config(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let raw = serde_yaml::from_str(&contents).map_err(|e| {
+            ChiaRpcError::Config(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        Ok(ChiaConfig { root, raw })
+    }
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+    pub fn self_hostname(&self) -> &str {
+        self.raw.self_hostname.as_deref().unwrap_or("localhost")
+    }
+    pub fn rpc_port(&self, service: ChiaService) -> u32 {
+        let port = match service {
+            ChiaService::Daemon => self.raw.daemon_port,
+            _ => self.service(service).and_then(|s| s.rpc_port),
+        };
+        port.unwrap_or_else(|| service.default_rpc_port())
+    }
+    /// Private certificate pair of the service, paths in the config are relative to the root.
+    pub fn ssl_files(&self, service: ChiaService) -> SslFiles {
+        let mut ssl_files = SslFiles::for_service(self.root.join("config").join("ssl"), service);
+        let ssl = match service {
+            ChiaService::Daemon => self.raw.daemon_ssl.as_ref(),
+            _ => self.service(service).and_then(|s| s.ssl.as_ref()),
+        };
+        if let Some(ssl) = ssl {
+            if let Some(crt) = &ssl.private_crt {
+                ssl_files.cert = self.root.join(crt);
+            }
+            if let Some(key) = &ssl.private_key {
+                ssl_files.key = self.root.join(key);
+            }
+        }
+        if let Some(crt) = self
+            .raw
+            .private_ssl_ca
+            .as_ref()
+            .and_then(|ca| ca.crt.as_ref())
+        {
+            ssl_files.ca = self.root.join(crt);
+        }
+        ssl_files
+    }
+    fn service(&self, service: ChiaService) -> Option<&RawService> {
+        match service {
+            ChiaService::Daemon => None,
+            ChiaService::FullNode => self.raw.full_node.as_ref(),
+            ChiaService::Wallet => self.raw.wallet.as_ref(),
+            ChiaService::Farmer => self.raw.farmer.as_ref(),
+            ChiaService::Harvester => self.raw.harvester.as_ref(),
+            ChiaService::DataLayer => self.raw.data_layer.as_ref(),
+            ChiaService::Crawler => self
+                .raw
+                .seeder
+                .as_ref()
+                .and_then(|seeder| seeder.crawler.as_ref()),
+            ChiaService::Timelord => self.raw.timelord.as_ref(),
+        }
+    }
+}
+
+/// Resolves `CHIA_ROOT`, defaulting to `~/.chia/mainnet` like the Chia tools.
+pub fn chia_root() -> Result<PathBuf, ChiaRpcError> {
+    let home = || {
+        dirs::home_dir().ok_or_else(|| {
+            ChiaRpcError::Config("Failed to find the home directory for CHIA_ROOT".to_string())
+        })
+    };
+    match env::var("CHIA_ROOT") {
+        Ok(root) if root == "~" => home(),
+        Ok(root) => match root.strip_prefix("~/") {
+            Some(rest) => Ok(home()?.join(rest)),
+            None => Ok(PathBuf::from(root)),
+        },
+        Err(_) => Ok(home()?.join(".chia").join("mainnet")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> ChiaConfig {
+        ChiaConfig {
+            root: PathBuf::from("/chia"),
+            raw: serde_yaml::from_str(yaml).unwrap(),
+        }
+    }
+
+    #[test]
+    fn ports_fall_back_to_the_defaults() {
+        let config = config(
+            "
+self_hostname: node.local
+daemon_port: 55401
+full_node:
+  rpc_port: 18555
+seeder:
+  crawler:
+    rpc_port: 18561
+",
+        );
+        assert_eq!(config.self_hostname(), "node.local");
+        assert_eq!(config.rpc_port(ChiaService::Daemon), 55401);
+        assert_eq!(config.rpc_port(ChiaService::FullNode), 18555);
+        assert_eq!(config.rpc_port(ChiaService::Crawler), 18561);
+        assert_eq!(config.rpc_port(ChiaService::Wallet), 9256);
+    }
+
+    #[test]
+    fn empty_config_uses_localhost() {
+        let config = config("{}");
+        assert_eq!(config.self_hostname(), "localhost");
+        assert_eq!(config.rpc_port(ChiaService::Daemon), 55400);
+    }
+
+    #[test]
+    fn ssl_paths_are_relative_to_the_root() {
+        let config = config(
+            "
+private_ssl_ca:
+  crt: config/ssl/ca/other_ca.crt
+daemon_ssl:
+  private_crt: config/ssl/daemon/d.crt
+full_node:
+  ssl:
+    private_crt: certs/node.crt
+    private_key: certs/node.key
+",
+        );
+        assert_eq!(
+            config.ssl_files(ChiaService::FullNode),
+            SslFiles {
+                cert: PathBuf::from("/chia/certs/node.crt"),
+                key: PathBuf::from("/chia/certs/node.key"),
+                ca: PathBuf::from("/chia/config/ssl/ca/other_ca.crt"),
+            }
+        );
+        let daemon = config.ssl_files(ChiaService::Daemon);
+        assert_eq!(daemon.cert, PathBuf::from("/chia/config/ssl/daemon/d.crt"));
+        assert_eq!(
+            daemon.key,
+            PathBuf::from("/chia/config/ssl/daemon/private_daemon.key")
+        );
+        assert_eq!(
+            config.ssl_files(ChiaService::Wallet).cert,
+            PathBuf::from("/chia/config/ssl/wallet/private_wallet.crt")
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use crate::clients::builder::{client_methods, ServiceClient};
+use crate::clients::common::*;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::crawler::{GetIpsAfterTimestamp, GetPeerCounts};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+use crate::clients::types::crawler::{PeerCounts, PeerIps};
+
+pub struct CrawlerClient {
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl ServiceClient for CrawlerClient {
+    const SERVICE: ChiaService = ChiaService::Crawler;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        CrawlerClient { transport }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        &self.transport
+    }
+}
+
+client_methods!(CrawlerClient);
+
+impl CrawlerClient {
+    pub async fn get_peer_counts(&self) -> Result<PeerCounts, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetPeerCounts {})
+            .await?
+            .peer_counts)
+    }
+    /// The ips of the peers seen after the unix timestamp `after`, `limit` at a time starting
+    /// at `offset`.
+    pub async fn get_ips_after_timestamp(
+        &self,
+        after: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Result<PeerIps, ChiaRpcError> {
+        let data = GetIpsAfterTimestamp {
+            after,
+            offset,
+            limit,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.peers)
+    }
+}
@@ -0,0 +1,417 @@
+use crate::clients::common::check_success;
+use crate::clients::config::ChiaConfig;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::events::ChiaEvent;
+use crate::clients::ssl::{ChiaService, SslFiles};
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, SplitSink, SplitStream};
+use futures_util::{FutureExt, SinkExt, Stream, StreamExt};
+use rustls::ClientConfig;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, oneshot};
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{
+    connect_async_tls_with_config, Connector, MaybeTlsStream, WebSocketStream,
+};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Envelope of every message sent to and received from the daemon.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WsMessage {
+    pub command: String,
+    pub ack: bool,
+    pub data: Value,
+    pub request_id: String,
+    pub destination: String,
+    pub origin: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DaemonStatus {
+    pub genesis_initialized: bool,
+}
+
+#[derive(Deserialize)]
+struct IsRunningResp {
+    is_running: bool,
+}
+
+#[derive(Deserialize)]
+struct RunningServicesResp {
+    running_services: Vec<String>,
+}
+
+const ORIGIN: &str = "client";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+struct Connection {
+    generation: u64,
+    sink: SplitSink<WsStream, Message>,
+}
+
+struct DaemonInner {
+    url: String,
+    tls: Arc<ClientConfig>,
+    timeout: Duration,
+    /// Only held to send on the socket, never while connecting.
+    connection: tokio::sync::Mutex<Option<Connection>>,
+    /// Held while opening a socket, so concurrent requests never open a second one.
+    connecting: tokio::sync::Mutex<()>,
+    generation: AtomicU64,
+    next_request: AtomicU64,
+    request_prefix: u64,
+    /// Waiting requests with the generation of the connection they were sent on, 0 until sent.
+    pending: Mutex<HashMap<String, (u64, oneshot::Sender<WsMessage>)>>,
+    registered: Mutex<Vec<String>>,
+    events: broadcast::Sender<WsMessage>,
+    closed: AtomicBool,
+}
+
+/// Client for the daemon WebSocket API, ie `wss://localhost:55400`.
+///
+/// Responses are matched to requests by `request_id`. When the connection drops it is
+/// re-established on the next request, or in the background when services are registered, and
+/// those services are registered again. Connecting, sending and waiting for the response share
+/// the timeout of each request.
+#[derive(Clone)]
+pub struct DaemonClient {
+    inner: Arc<DaemonInner>,
+}
+
+impl DaemonClient {
+    pub async fn connect(host: &str, port: u32, ssl_path: &str) -> Result<Self, ChiaRpcError> {
+        Self::connect_with(
+            &format!("wss://{}:{}", host, port),
+            &SslFiles::for_service(ssl_path, ChiaService::Daemon),
+        )
+        .await
+    }
+    /// Connects with the daemon port and certificates found in `CHIA_ROOT`.
+    pub async fn from_chia_root() -> Result<Self, ChiaRpcError> {
+        let config = ChiaConfig::load()?;
+        Self::connect_with(
+            &format!(
+                "wss://{}:{}",
+                config.self_hostname(),
+                config.rpc_port(ChiaService::Daemon)
+            ),
+            &config.ssl_files(ChiaService::Daemon),
+        )
+        .await
+    }
+    pub async fn connect_with(url: &str, ssl_files: &SslFiles) -> Result<Self, ChiaRpcError> {
+        Self::connect_with_timeout(url, ssl_files, DEFAULT_TIMEOUT).await
+    }
+    /// Like [`Self::connect_with`] with another timeout than the default 30 seconds.
+    pub async fn connect_with_timeout(
+        url: &str,
+        ssl_files: &SslFiles,
+        timeout: Duration,
+    ) -> Result<Self, ChiaRpcError> {
+        let (events, _) = broadcast::channel(1024);
+        let client = DaemonClient {
+            inner: Arc::new(DaemonInner {
+                url: url.to_string(),
+                tls: Arc::new(ssl_files.client_config()?),
+                timeout,
+                connection: tokio::sync::Mutex::new(None),
+                connecting: tokio::sync::Mutex::new(()),
+                generation: AtomicU64::new(0),
+                next_request: AtomicU64::new(0),
+                request_prefix: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_nanos() as u64)
+                    .unwrap_or_default(),
+                pending: Mutex::new(HashMap::new()),
+                registered: Mutex::new(vec![]),
+                events,
+                closed: AtomicBool::new(false),
+            }),
+        };
+        DaemonInner::connect(&client.inner).await?;
+        Ok(client)
+    }
+    /// Sends `command` to the daemon and waits for the matching response `data`.
+    pub async fn request(&self, command: &str, data: Value) -> Result<Value, ChiaRpcError> {
+        let deadline = Instant::now() + self.inner.timeout;
+        let (tx, rx) = oneshot::channel();
+        let message = self.inner.message(command, data);
+        self.inner
+            .pending
+            .lock()
+            .unwrap()
+            .insert(message.request_id.clone(), (0, tx));
+        let sent = tokio::time::timeout_at(deadline, DaemonInner::send(&self.inner, &message))
+            .await
+            .unwrap_or_else(|_| Err(ChiaRpcError::Timeout(command.to_string())));
+        if let Err(e) = sent {
+            self.inner
+                .pending
+                .lock()
+                .unwrap()
+                .remove(&message.request_id);
+            return Err(e);
+        }
+        let response = match tokio::time::timeout_at(deadline, rx).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => return Err(ws_error(WsError::ConnectionClosed)),
+            Err(_) => {
+                self.inner
+                    .pending
+                    .lock()
+                    .unwrap()
+                    .remove(&message.request_id);
+                return Err(ChiaRpcError::Timeout(command.to_string()));
+            }
+        };
+        check_success(command, &response.data)?;
+        Ok(response.data)
+    }
+    async fn request_as<T: DeserializeOwned>(
+        &self,
+        command: &str,
+        data: Value,
+    ) -> Result<T, ChiaRpcError> {
+        let value = self.request(command, data).await?;
+        serde_path_to_error::deserialize(&value).map_err(|e| ChiaRpcError::Decode {
+            endpoint: command.to_string(),
+            path: e.path().to_string(),
+            source: e.into_inner(),
+            body: value.to_string(),
+        })
+    }
+    /// Registers this connection as `service`, ie `wallet_ui` or `metrics`, to receive its events.
+    pub async fn register_service(&self, service: &str) -> Result<(), ChiaRpcError> {
+        self.request("register_service", json!({ "service": service }))
+            .await?;
+        let mut registered = self.inner.registered.lock().unwrap();
+        if !registered.iter().any(|s| s == service) {
+            registered.push(service.to_string());
+        }
+        Ok(())
+    }
+    pub async fn is_running(&self, service: ChiaService) -> Result<bool, ChiaRpcError> {
+        Ok(self
+            .request_as::<IsRunningResp>("is_running", json!({ "service": service.daemon_name() }))
+            .await?
+            .is_running)
+    }
+    pub async fn start_service(&self, service: ChiaService) -> Result<(), ChiaRpcError> {
+        self.request(
+            "start_service",
+            json!({ "service": service.daemon_name(), "testing": false }),
+        )
+        .await?;
+        Ok(())
+    }
+    pub async fn stop_service(&self, service: ChiaService) -> Result<(), ChiaRpcError> {
+        self.request("stop_service", json!({ "service": service.daemon_name() }))
+            .await?;
+        Ok(())
+    }
+    pub async fn running_services(&self) -> Result<Vec<String>, ChiaRpcError> {
+        Ok(self
+            .request_as::<RunningServicesResp>("running_services", json!({}))
+            .await?
+            .running_services)
+    }
+    pub async fn get_status(&self) -> Result<DaemonStatus, ChiaRpcError> {
+        self.request_as("get_status", json!({})).await
+    }
+    /// Messages pushed by the daemon that are not responses to a request.
+    pub fn subscribe(&self) -> broadcast::Receiver<WsMessage> {
+        self.inner.events.subscribe()
+    }
+    /// Typed stream of the events pushed to the services this connection registered as.
+    pub fn events(&self) -> impl Stream<Item = ChiaEvent> {
+        stream::unfold(self.subscribe(), |mut events| async move {
+            let event = match events.recv().await {
+                Ok(message) => ChiaEvent::from(message),
+                Err(RecvError::Lagged(skipped)) => ChiaEvent::Lagged(skipped),
+                Err(RecvError::Closed) => return None,
+            };
+            Some((event, events))
+        })
+    }
+    /// Registers as each of `services` and returns the event stream. The daemon forwards a
+    /// notification only to the services its producer addresses:
+    ///
+    /// - `wallet_ui` receives the full node's peaks and sync changes, the wallet's
+    ///   `state_changed` events and the farmer's signage points and farming info.
+    /// - `metrics` receives the full node's blocks and signage points, the farmer's signage
+    ///   points, farming info, proofs and partials, and the harvester's farming info.
+    ///
+    /// A notification addressed to several of the registered services arrives once for each.
+    pub async fn state_changed_events(
+        &self,
+        services: &[&str],
+    ) -> Result<impl Stream<Item = ChiaEvent>, ChiaRpcError> {
+        let events = self.events();
+        for service in services {
+            self.register_service(service).await?;
+        }
+        Ok(events)
+    }
+    pub async fn close(&self) -> Result<(), ChiaRpcError> {
+        self.inner.closed.store(true, Ordering::SeqCst);
+        if let Some(mut connection) = self.inner.connection.lock().await.take() {
+            connection.sink.close().await.map_err(ws_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl DaemonInner {
+    fn message(&self, command: &str, data: Value) -> WsMessage {
+        WsMessage {
+            command: command.to_string(),
+            ack: false,
+            data,
+            request_id: format!(
+                "{:016x}{:016x}",
+                self.request_prefix,
+                self.next_request.fetch_add(1, Ordering::SeqCst)
+            ),
+            destination: "daemon".to_string(),
+            origin: ORIGIN.to_string(),
+        }
+    }
+    async fn send(inner: &Arc<Self>, message: &WsMessage) -> Result<(), ChiaRpcError> {
+        let text = serde_json::to_string(message).map_err(|e| {
+            ChiaRpcError::InvalidInput(format!("Failed to serialize {}: {}", message.command, e))
+        })?;
+        let mut retried = false;
+        loop {
+            DaemonInner::connect(inner).await?;
+            let mut connection = inner.connection.lock().await;
+            // Dropped again by its reader in the meantime.
+            let Some(c) = connection.as_mut() else {
+                continue;
+            };
+            if let Some((generation, _)) =
+                inner.pending.lock().unwrap().get_mut(&message.request_id)
+            {
+                *generation = c.generation;
+            }
+            match c.sink.send(Message::Text(text.clone())).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    *connection = None;
+                    if retried {
+                        return Err(ws_error(e));
+                    }
+                    retried = true;
+                }
+            }
+        }
+    }
+    /// Connects unless a connection was already established in the meantime.
+    async fn connect(inner: &Arc<Self>) -> Result<(), ChiaRpcError> {
+        if inner.connection.lock().await.is_some() {
+            return Ok(());
+        }
+        let _connecting = inner.connecting.lock().await;
+        if inner.connection.lock().await.is_some() {
+            return Ok(());
+        }
+        let connection = tokio::time::timeout(inner.timeout, DaemonInner::open(inner))
+            .await
+            .map_err(|_| ChiaRpcError::Timeout(inner.url.clone()))??;
+        *inner.connection.lock().await = Some(connection);
+        Ok(())
+    }
+    /// Opens a new connection, callers hold the `connecting` lock. Boxed because the spawned
+    /// reader reconnects through this function again.
+    fn open(inner: &Arc<Self>) -> BoxFuture<'_, Result<Connection, ChiaRpcError>> {
+        async move {
+            if inner.closed.load(Ordering::SeqCst) {
+                return Err(ws_error(WsError::AlreadyClosed));
+            }
+            let (stream, _) = connect_async_tls_with_config(
+                inner.url.as_str(),
+                None,
+                false,
+                Some(Connector::Rustls(inner.tls.clone())),
+            )
+            .await
+            .map_err(ws_error)?;
+            let (mut sink, stream) = stream.split();
+            let registered = inner.registered.lock().unwrap().clone();
+            for service in registered {
+                let message = inner.message("register_service", json!({ "service": service }));
+                let text = serde_json::to_string(&message).unwrap_or_default();
+                sink.send(Message::Text(text)).await.map_err(ws_error)?;
+            }
+            let generation = inner.generation.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::spawn(DaemonInner::read(inner.clone(), generation, stream));
+            Ok(Connection { generation, sink })
+        }
+        .boxed()
+    }
+    async fn read(inner: Arc<Self>, generation: u64, mut stream: SplitStream<WsStream>) {
+        while let Some(Ok(message)) = stream.next().await {
+            let message = match message {
+                Message::Text(text) => serde_json::from_str::<WsMessage>(&text),
+                Message::Binary(bytes) => serde_json::from_slice::<WsMessage>(&bytes),
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(message) = message else {
+                continue;
+            };
+            let pending = inner.pending.lock().unwrap().remove(&message.request_id);
+            match pending {
+                Some((_, tx)) => {
+                    let _ = tx.send(message);
+                }
+                None => {
+                    if !message.ack {
+                        let _ = inner.events.send(message);
+                    }
+                }
+            }
+        }
+        {
+            let mut connection = inner.connection.lock().await;
+            if matches!(connection.as_ref(), Some(c) if c.generation == generation) {
+                *connection = None;
+            }
+            // Requests in flight on a newer connection are left waiting for their response.
+            inner
+                .pending
+                .lock()
+                .unwrap()
+                .retain(|_, (sent_on, _)| *sent_on != generation);
+        }
+        let has_registered = !inner.registered.lock().unwrap().is_empty();
+        if has_registered && !inner.closed.load(Ordering::SeqCst) {
+            tokio::spawn(DaemonInner::reconnect(inner));
+        }
+    }
+    async fn reconnect(inner: Arc<Self>) {
+        let mut delay = RECONNECT_DELAY;
+        while !inner.closed.load(Ordering::SeqCst) {
+            tokio::time::sleep(delay).await;
+            if DaemonInner::connect(&inner).await.is_ok() {
+                return;
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+}
+
+fn ws_error(e: WsError) -> ChiaRpcError {
+    ChiaRpcError::WebSocket(Box::new(e))
+}
@@ -0,0 +1,144 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use std::sync::Arc;
+
+use crate::clients::builder::{client_methods, ServiceClient};
+use crate::clients::common::*;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::data_layer::{
+    AddMirror, BatchUpdate, CreateDataStore, GetKeysValues, GetKvDiff, GetMirrors, GetOwnedStores,
+    GetRoot, GetRootHistory, GetSyncStatus, GetValue, Subscribe, Unsubscribe,
+};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+use crate::clients::types::data_layer::{
+    Change, DataStoreSyncStatus, HexBytes, KeyValue, KvDiff, Mirror, Root, RootHistoryEntry,
+};
+
+pub struct DataLayerClient {
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl ServiceClient for DataLayerClient {
+    const SERVICE: ChiaService = ChiaService::DataLayer;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        DataLayerClient { transport }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        &self.transport
+    }
+}
+
+client_methods!(DataLayerClient);
+
+impl DataLayerClient {
+    /// Creates a store on chain and returns its id, the store is usable once the transaction
+    /// is confirmed.
+    pub async fn create_data_store(&self, fee: Option<u64>) -> Result<Bytes32, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &CreateDataStore { fee })
+            .await?
+            .id)
+    }
+    pub async fn get_owned_stores(&self) -> Result<Vec<Bytes32>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetOwnedStores {})
+            .await?
+            .store_ids)
+    }
+    /// Applies the changes in one new root and returns the id of the update transaction.
+    pub async fn batch_update(
+        &self,
+        id: &Bytes32,
+        changelist: Vec<Change>,
+        fee: Option<u64>,
+    ) -> Result<Bytes32, ChiaRpcError> {
+        let data = BatchUpdate {
+            id,
+            changelist: &changelist,
+            fee,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.tx_id)
+    }
+    /// Reads every key and value at `root_hash`, or at the current root when `None`.
+    pub async fn get_keys_values(
+        &self,
+        id: &Bytes32,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<Vec<KeyValue>, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetKeysValues { id, root_hash })
+                .await?
+                .keys_values,
+        )
+    }
+    pub async fn get_value(
+        &self,
+        id: &Bytes32,
+        key: &HexBytes,
+        root_hash: Option<&Bytes32>,
+    ) -> Result<HexBytes, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetValue { id, key, root_hash })
+                .await?
+                .value,
+        )
+    }
+    pub async fn get_root(&self, id: &Bytes32) -> Result<Root, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetRoot { id })
+            .await?
+            .root)
+    }
+    pub async fn get_root_history(
+        &self,
+        id: &Bytes32,
+    ) -> Result<Vec<RootHistoryEntry>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetRootHistory { id })
+            .await?
+            .root_history)
+    }
+    /// The keys inserted and deleted going from root `hash_1` to root `hash_2`.
+    pub async fn get_kv_diff(
+        &self,
+        id: &Bytes32,
+        hash_1: &Bytes32,
+        hash_2: &Bytes32,
+    ) -> Result<Vec<KvDiff>, ChiaRpcError> {
+        let data = GetKvDiff { id, hash_1, hash_2 };
+        Ok(request(self.transport.as_ref(), &data).await?.diff)
+    }
+    /// Follows a store owned by someone else, downloading its data from the mirrors and `urls`.
+    pub async fn subscribe(&self, id: &Bytes32, urls: Vec<String>) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &Subscribe { id, urls: &urls }).await?;
+        Ok(())
+    }
+    /// With `retain` the downloaded files of the store are kept on disk.
+    pub async fn unsubscribe(&self, id: &Bytes32, retain: bool) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &Unsubscribe { id, retain }).await?;
+        Ok(())
+    }
+    /// Announces `urls` serving the store on chain, locking `amount` mojos in the mirror coin.
+    pub async fn add_mirror(
+        &self,
+        id: &Bytes32,
+        urls: Vec<String>,
+        amount: u64,
+        fee: Option<u64>,
+    ) -> Result<(), ChiaRpcError> {
+        let data = AddMirror {
+            id,
+            urls: &urls,
+            amount,
+            fee,
+        };
+        request(self.transport.as_ref(), &data).await?;
+        Ok(())
+    }
+    pub async fn get_mirrors(&self, id: &Bytes32) -> Result<Vec<Mirror>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetMirrors { id })
+            .await?
+            .mirrors)
+    }
+    pub async fn get_sync_status(&self, id: &Bytes32) -> Result<DataStoreSyncStatus, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetSyncStatus { id })
+            .await?
+            .sync_status)
+    }
+}
@@ -0,0 +1,176 @@
+use reqwest::StatusCode;
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
+
+#[derive(Debug)]
+pub enum ChiaRpcError {
+    /// The request never produced a response: connection, TLS or timeout failures.
+    Transport(reqwest::Error),
+    /// The node could not be reached and there is no reqwest error to carry, ie a transport
+    /// failure replayed from a cassette.
+    Unreachable(String),
+    /// The node answered with a non-200 status code.
+    HttpStatus {
+        url: String,
+        status: StatusCode,
+        body: String,
+    },
+    /// The node answered `"success": false`, `message` is the node's `error` field.
+    Rpc { endpoint: String, message: String },
+    /// The response body could not be decoded into the expected type.
+    Decode {
+        endpoint: String,
+        body: String,
+        path: String,
+        source: serde_json::Error,
+    },
+    /// The node answered successfully but not with what was asked for, such as fewer heights
+    /// than requested.
+    UnexpectedResponse { endpoint: String, message: String },
+    /// The daemon WebSocket connection failed or was closed.
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    /// No response arrived in time, carries the endpoint or command.
+    Timeout(String),
+    /// Invalid client configuration, such as missing or unreadable certificates.
+    Config(String),
+    /// Invalid arguments passed to a client method.
+    InvalidInput(String),
+}
+
+impl ChiaRpcError {
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            ChiaRpcError::Transport(e) => e.is_timeout(),
+            ChiaRpcError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+    /// Whether the node answered that the requested record does not exist, ie an unknown coin
+    /// or a height or block it has not reached.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            ChiaRpcError::Rpc { message, .. } => {
+                let message = message.to_lowercase();
+                message.contains("not found") || message.contains("not in blockchain")
+            }
+            _ => false,
+        }
+    }
+    /// Whether the failure is likely transient, ie the node is restarting or overloaded, so the
+    /// same request may succeed later. Errors reported by the node itself are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ChiaRpcError::Transport(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            ChiaRpcError::HttpStatus { status, .. } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            ChiaRpcError::Unreachable(_)
+            | ChiaRpcError::WebSocket(_)
+            | ChiaRpcError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for ChiaRpcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChiaRpcError::Transport(e) => write!(f, "Transport error: {}", e),
+            ChiaRpcError::Unreachable(msg) => write!(f, "Transport error: {}", msg),
+            ChiaRpcError::HttpStatus { url, status, body } => {
+                write!(f, "Bad Status Code: {}, for URL {}: {}", status, url, body)
+            }
+            ChiaRpcError::Rpc { endpoint, message } => {
+                write!(f, "RPC call to {} failed: {}", endpoint, message)
+            }
+            ChiaRpcError::Decode {
+                endpoint,
+                body,
+                path,
+                source,
+            } => write!(
+                f,
+                "Failed to Parse Json from {} at '{}': {},\r\n {}",
+                endpoint, path, source, body
+            ),
+            ChiaRpcError::UnexpectedResponse { endpoint, message } => {
+                write!(f, "Unexpected response from {}: {}", endpoint, message)
+            }
+            ChiaRpcError::WebSocket(e) => write!(f, "WebSocket error: {}", e),
+            ChiaRpcError::Timeout(endpoint) => write!(f, "Timed out waiting for {}", endpoint),
+            ChiaRpcError::Config(msg) => write!(f, "Invalid configuration: {}", msg),
+            ChiaRpcError::InvalidInput(msg) => write!(f, "Invalid argument: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ChiaRpcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChiaRpcError::Transport(e) => Some(e),
+            ChiaRpcError::Decode { source, .. } => Some(source),
+            ChiaRpcError::WebSocket(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ChiaRpcError {
+    fn from(e: reqwest::Error) -> Self {
+        ChiaRpcError::Transport(e)
+    }
+}
+
+impl From<ChiaRpcError> for std::io::Error {
+    fn from(e: ChiaRpcError) -> Self {
+        let kind = match &e {
+            err if err.is_timeout() => ErrorKind::TimedOut,
+            ChiaRpcError::InvalidInput(_) => ErrorKind::InvalidInput,
+            _ => ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc(message: &str) -> ChiaRpcError {
+        ChiaRpcError::Rpc {
+            endpoint: "get_coin_record_by_name".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// The errors the full node raises for unknown coins, heights and blocks. A change in their
+    /// wording would turn not found answers into failures in quorum reads.
+    #[test]
+    fn not_found_matches_the_node_messages() {
+        let hash = "01".repeat(32);
+        for message in [
+            format!("Coin record 0x{} not found", hash),
+            "Block height 100 not found in chain".to_string(),
+            "Height not in blockchain: 100".to_string(),
+            format!("Block {} not found", hash),
+        ] {
+            assert!(rpc(&message).is_not_found(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn other_errors_are_not_not_found() {
+        assert!(!rpc("Invalid coin name").is_not_found());
+        assert!(!ChiaRpcError::Unreachable("not found".to_string()).is_not_found());
+        let status = ChiaRpcError::HttpStatus {
+            url: "get_coin_record_by_name".to_string(),
+            status: StatusCode::NOT_FOUND,
+            body: "not found".to_string(),
+        };
+        assert!(!status.is_not_found());
+    }
+}
@@ -0,0 +1,293 @@
+use crate::clients::daemon::WsMessage;
+use crate::clients::types::farmer::{NewFarmingInfo, NewSignagePoint};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Notifications pushed by the services through the daemon, see [`crate::clients::daemon::DaemonClient::events`].
+#[derive(Debug)]
+pub enum ChiaEvent {
+    /// The full node reached a new peak or entered or left sync mode, `data` is the full
+    /// `get_blockchain_state` response. Compare `synced` with the previous event to tell sync
+    /// changes of the full node.
+    NewPeak {
+        origin: String,
+        height: Option<u32>,
+        synced: Option<bool>,
+        data: Value,
+    },
+    /// A wallet received a coin.
+    CoinAdded {
+        wallet_id: Option<u32>,
+        data: Value,
+    },
+    /// A wallet transaction was sent or updated (`tx_update`).
+    TransactionSent {
+        wallet_id: Option<u32>,
+        data: Value,
+    },
+    /// The sync state of a wallet changed, the full node reports its sync changes as
+    /// [`ChiaEvent::NewPeak`].
+    SyncChanged {
+        origin: String,
+    },
+    NewSignagePoint(NewSignagePoint),
+    NewFarmingInfo(NewFarmingInfo),
+    /// Any other message, including events this crate does not type yet.
+    Other(WsMessage),
+    /// The receiver fell behind and this many events were dropped.
+    Lagged(u64),
+}
+
+impl From<WsMessage> for ChiaEvent {
+    fn from(message: WsMessage) -> Self {
+        match message.command.as_str() {
+            "get_blockchain_state" => {
+                let state = &message.data["blockchain_state"];
+                ChiaEvent::NewPeak {
+                    height: state["peak"]["height"].as_u64().map(|h| h as u32),
+                    synced: state["sync"]["synced"].as_bool(),
+                    origin: message.origin,
+                    data: message.data,
+                }
+            }
+            "new_signage_point" => {
+                match NewSignagePoint::deserialize(&message.data["signage_point"]) {
+                    Ok(sp) => ChiaEvent::NewSignagePoint(sp),
+                    Err(_) => ChiaEvent::Other(message),
+                }
+            }
+            "new_farming_info" => {
+                match NewFarmingInfo::deserialize(&message.data["farming_info"]) {
+                    Ok(info) => ChiaEvent::NewFarmingInfo(info),
+                    Err(_) => ChiaEvent::Other(message),
+                }
+            }
+            "state_changed" => {
+                let wallet_id = message.data["wallet_id"].as_u64().map(|id| id as u32);
+                match message.data["state"].as_str() {
+                    Some("coin_added") => ChiaEvent::CoinAdded {
+                        wallet_id,
+                        data: message.data,
+                    },
+                    Some("tx_update") => ChiaEvent::TransactionSent {
+                        wallet_id,
+                        data: message.data,
+                    },
+                    Some("sync_changed") | Some("sync_mode") => ChiaEvent::SyncChanged {
+                        origin: message.origin,
+                    },
+                    Some("new_peak") => ChiaEvent::NewPeak {
+                        origin: message.origin,
+                        height: message.data["peak_height"]
+                            .as_u64()
+                            .or_else(|| message.data["height"].as_u64())
+                            .map(|h| h as u32),
+                        synced: None,
+                        data: message.data,
+                    },
+                    _ => ChiaEvent::Other(message),
+                }
+            }
+            _ => ChiaEvent::Other(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hash(byte: u8) -> String {
+        format!("0x{}", format!("{:02x}", byte).repeat(32))
+    }
+
+    fn message(command: &str, origin: &str, data: Value) -> WsMessage {
+        WsMessage {
+            command: command.to_string(),
+            ack: false,
+            data,
+            request_id: String::new(),
+            destination: "wallet_ui".to_string(),
+            origin: origin.to_string(),
+        }
+    }
+
+    fn wallet_state(state: &str, mut data: Value) -> ChiaEvent {
+        data["state"] = json!(state);
+        data["success"] = json!(true);
+        message("state_changed", "chia_wallet", data).into()
+    }
+
+    fn blockchain_state(synced: bool, sync_mode: bool) -> ChiaEvent {
+        let data = json!({
+            "blockchain_state": {
+                "peak": { "height": 4_500_000, "header_hash": hash(1) },
+                "sync": {
+                    "synced": synced,
+                    "sync_mode": sync_mode,
+                    "sync_progress_height": 4_400_000,
+                    "sync_tip_height": 4_500_000,
+                },
+                "difficulty": 3_008,
+            },
+            "success": true,
+        });
+        message("get_blockchain_state", "chia_full_node", data).into()
+    }
+
+    #[test]
+    fn blockchain_state_is_a_new_peak_with_the_sync_flags() {
+        match blockchain_state(true, false) {
+            ChiaEvent::NewPeak {
+                origin,
+                height,
+                synced,
+                data,
+            } => {
+                assert_eq!(origin, "chia_full_node");
+                assert_eq!(height, Some(4_500_000));
+                assert_eq!(synced, Some(true));
+                assert_eq!(data["blockchain_state"]["difficulty"], json!(3_008));
+            }
+            event => panic!("{:?}", event),
+        }
+        assert!(matches!(
+            blockchain_state(false, true),
+            ChiaEvent::NewPeak {
+                synced: Some(false),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn wallet_new_peak_reads_the_peak_height() {
+        let event = wallet_state(
+            "new_peak",
+            json!({ "wallet_id": null, "peak_height": 4_500_001 }),
+        );
+        assert!(matches!(
+            event,
+            ChiaEvent::NewPeak {
+                height: Some(4_500_001),
+                synced: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn coin_added_carries_the_wallet_id() {
+        match wallet_state("coin_added", json!({ "wallet_id": 1 })) {
+            ChiaEvent::CoinAdded { wallet_id, data } => {
+                assert_eq!(wallet_id, Some(1));
+                assert_eq!(data["state"], json!("coin_added"));
+            }
+            event => panic!("{:?}", event),
+        }
+    }
+
+    #[test]
+    fn tx_update_is_a_transaction_sent() {
+        let data = json!({
+            "wallet_id": 2,
+            "additional_data": { "transaction": { "name": hash(2), "confirmed": false } },
+        });
+        match wallet_state("tx_update", data) {
+            ChiaEvent::TransactionSent { wallet_id, data } => {
+                assert_eq!(wallet_id, Some(2));
+                assert_eq!(
+                    data["additional_data"]["transaction"]["name"],
+                    json!(hash(2))
+                );
+            }
+            event => panic!("{:?}", event),
+        }
+    }
+
+    #[test]
+    fn sync_states_are_sync_changed() {
+        for state in ["sync_changed", "sync_mode"] {
+            match wallet_state(state, json!({ "wallet_id": null })) {
+                ChiaEvent::SyncChanged { origin } => assert_eq!(origin, "chia_wallet"),
+                event => panic!("{}: {:?}", state, event),
+            }
+        }
+    }
+
+    #[test]
+    fn farmer_signage_point() {
+        let data = json!({
+            "signage_point": {
+                "challenge_hash": hash(3),
+                "challenge_chain_sp": hash(4),
+                "reward_chain_sp": hash(5),
+                "difficulty": 3_008,
+                "sub_slot_iters": 578_813_952,
+                "signage_point_index": 17,
+                "peak_height": 4_500_000,
+            },
+            "proofs": [],
+            "success": true,
+        });
+        match ChiaEvent::from(message("new_signage_point", "chia_farmer", data)) {
+            ChiaEvent::NewSignagePoint(sp) => {
+                assert_eq!(sp.signage_point_index, 17);
+                assert_eq!(sp.peak_height, 4_500_000);
+                assert_eq!(sp.difficulty, 3_008);
+            }
+            event => panic!("{:?}", event),
+        }
+    }
+
+    #[test]
+    fn farmer_farming_info() {
+        let data = json!({
+            "farming_info": {
+                "challenge_hash": hash(3),
+                "signage_point": hash(4),
+                "passed_filter": 3,
+                "proofs": 1,
+                "total_plots": 1_500,
+                "timestamp": 1_700_000_000u64,
+            },
+            "success": true,
+        });
+        match ChiaEvent::from(message("new_farming_info", "chia_farmer", data)) {
+            ChiaEvent::NewFarmingInfo(info) => {
+                assert_eq!(info.passed_filter, 3);
+                assert_eq!(info.proofs, 1);
+                assert_eq!(info.total_plots, 1_500);
+                assert_eq!(info.timestamp, 1_700_000_000);
+            }
+            event => panic!("{:?}", event),
+        }
+    }
+
+    #[test]
+    fn unknown_and_malformed_messages_are_other() {
+        let malformed = json!({ "signage_point": { "challenge_hash": "0x01" } });
+        for ws in [
+            message(
+                "state_changed",
+                "chia_wallet",
+                json!({ "state": "wallet_created", "wallet_id": 3 }),
+            ),
+            message("state_changed", "chia_wallet", json!({ "wallet_id": 3 })),
+            message("new_signage_point", "chia_farmer", malformed),
+            message(
+                "new_farming_info",
+                "chia_farmer",
+                json!({ "success": true }),
+            ),
+            message("new_plots", "chia_harvester", json!({ "plots": [] })),
+        ] {
+            let command = ws.command.clone();
+            match ChiaEvent::from(ws) {
+                ChiaEvent::Other(other) => assert_eq!(other.command, command),
+                event => panic!("{}: {:?}", command, event),
+            }
+        }
+    }
+}
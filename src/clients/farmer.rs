@@ -0,0 +1,185 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use std::sync::Arc;
+
+use crate::clients::builder::{client_methods, ServiceClient};
+use crate::clients::common::*;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::farmer::{
+    GetHarvesterPlotsDuplicates, GetHarvesterPlotsInvalid, GetHarvesterPlotsKeysMissing,
+    GetHarvesterPlotsValid, GetHarvesters, GetHarvestersSummary, GetPoolLoginLink, GetPoolState,
+    GetRewardTargets, GetSignagePoint, GetSignagePoints, SetPayoutInstructions, SetRewardTargets,
+};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+use crate::clients::types::farmer::{FarmerSignagePoint, PoolState, RewardTargets};
+use crate::clients::types::harvester::{
+    HarvesterDetails, HarvesterSummary, PaginatedPlots, PlotFilter, PlotInfo, PlotIssues,
+};
+
+pub struct FarmerClient {
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl ServiceClient for FarmerClient {
+    const SERVICE: ChiaService = ChiaService::Farmer;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        FarmerClient { transport }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        &self.transport
+    }
+}
+
+client_methods!(FarmerClient);
+
+impl FarmerClient {
+    pub async fn get_signage_point(
+        &self,
+        sp_hash: &Bytes32,
+    ) -> Result<FarmerSignagePoint, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetSignagePoint { sp_hash })
+                .await?
+                .signage_point,
+        )
+    }
+    pub async fn get_signage_points(&self) -> Result<Vec<FarmerSignagePoint>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetSignagePoints {})
+            .await?
+            .signage_points)
+    }
+    /// With `search_for_private_key` the farmer also reports whether it holds the keys of the
+    /// targets, searching at most `max_ph_to_search` puzzle hashes.
+    pub async fn get_reward_targets(
+        &self,
+        search_for_private_key: bool,
+        max_ph_to_search: Option<u32>,
+    ) -> Result<RewardTargets, ChiaRpcError> {
+        let data = GetRewardTargets {
+            search_for_private_key,
+            max_ph_to_search,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.targets)
+    }
+    pub async fn set_reward_targets(
+        &self,
+        farmer_target: Option<&str>,
+        pool_target: Option<&str>,
+    ) -> Result<(), ChiaRpcError> {
+        let data = SetRewardTargets {
+            farmer_target,
+            pool_target,
+        };
+        request(self.transport.as_ref(), &data).await?;
+        Ok(())
+    }
+    pub async fn get_pool_state(&self) -> Result<Vec<PoolState>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetPoolState {})
+            .await?
+            .pool_state)
+    }
+    pub async fn set_payout_instructions(
+        &self,
+        launcher_id: &Bytes32,
+        payout_instructions: &str,
+    ) -> Result<(), ChiaRpcError> {
+        let data = SetPayoutInstructions {
+            launcher_id,
+            payout_instructions,
+        };
+        request(self.transport.as_ref(), &data).await?;
+        Ok(())
+    }
+    pub async fn get_harvesters(&self) -> Result<Vec<HarvesterDetails>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetHarvesters {})
+            .await?
+            .harvesters)
+    }
+    /// The plots that are not farming on any connected harvester, including plot ids loaded by
+    /// more than one harvester.
+    pub async fn get_plot_issues(&self) -> Result<PlotIssues, ChiaRpcError> {
+        Ok(PlotIssues::from_harvesters(&self.get_harvesters().await?))
+    }
+    pub async fn get_harvesters_summary(&self) -> Result<Vec<HarvesterSummary>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetHarvestersSummary {})
+            .await?
+            .harvesters)
+    }
+    pub async fn get_harvester_plots_valid(
+        &self,
+        node_id: &Bytes32,
+        page: u32,
+        page_size: u32,
+        filter: Vec<PlotFilter>,
+        sort_key: &str,
+        reverse: bool,
+    ) -> Result<PaginatedPlots<PlotInfo>, ChiaRpcError> {
+        let data = GetHarvesterPlotsValid {
+            node_id,
+            page,
+            page_size,
+            filter: &filter,
+            sort_key,
+            reverse,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.page)
+    }
+    pub async fn get_harvester_plots_invalid(
+        &self,
+        node_id: &Bytes32,
+        page: u32,
+        page_size: u32,
+        filter: Vec<String>,
+        reverse: bool,
+    ) -> Result<PaginatedPlots<String>, ChiaRpcError> {
+        let data = GetHarvesterPlotsInvalid {
+            node_id,
+            page,
+            page_size,
+            filter: &filter,
+            reverse,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.page)
+    }
+    pub async fn get_harvester_plots_keys_missing(
+        &self,
+        node_id: &Bytes32,
+        page: u32,
+        page_size: u32,
+        filter: Vec<String>,
+        reverse: bool,
+    ) -> Result<PaginatedPlots<String>, ChiaRpcError> {
+        let data = GetHarvesterPlotsKeysMissing {
+            node_id,
+            page,
+            page_size,
+            filter: &filter,
+            reverse,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.page)
+    }
+    pub async fn get_harvester_plots_duplicates(
+        &self,
+        node_id: &Bytes32,
+        page: u32,
+        page_size: u32,
+        filter: Vec<String>,
+        reverse: bool,
+    ) -> Result<PaginatedPlots<String>, ChiaRpcError> {
+        let data = GetHarvesterPlotsDuplicates {
+            node_id,
+            page,
+            page_size,
+            filter: &filter,
+            reverse,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.page)
+    }
+    pub async fn get_pool_login_link(&self, launcher_id: &Bytes32) -> Result<String, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetPoolLoginLink { launcher_id })
+                .await?
+                .login_link,
+        )
+    }
+}
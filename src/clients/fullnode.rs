@@ -3,158 +3,227 @@ use druid_garden_chia_types::blockchain::blockchain_state::BlockchainState;
 use druid_garden_chia_types::blockchain::coin_record::CoinRecord;
 use druid_garden_chia_types::blockchain::coin_spend::CoinSpend;
 use druid_garden_chia_types::blockchain::full_block::FullBlock;
-use druid_garden_chia_types::blockchain::mem_pool_item::MemPoolItem;
+use druid_garden_chia_types::blockchain::mempool_item::MempoolItem;
 use druid_garden_chia_types::blockchain::network_info::NetworkInfo;
 use druid_garden_chia_types::blockchain::signage_point_or_eos::SignagePointOrEOS;
 use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
 use druid_garden_chia_types::blockchain::spend_bundle::SpendBundle;
 use druid_garden_chia_types::blockchain::tx_status::TXStatus;
 use druid_garden_chia_types::blockchain::unfinished_block::UnfinishedBlock;
-use reqwest::Client;
-use serde_json::{json, Map};
 use std::collections::HashMap;
-use std::io::{Error, ErrorKind};
+use std::sync::Arc;
 
+use crate::clients::builder::{client_methods, ServiceClient};
 use crate::clients::common::*;
-use crate::clients::responses::{
-    AdditionsAndRemovalsResp, BlockRecordAryResp, BlockRecordResp, BlockchainStateResp,
-    CoinRecordAryResp, CoinRecordResp, CoinSpendResp, FullBlockAryResp, FullBlockResp,
-    InitialFreezePeriodResp, MempoolItemResp, MempoolItemsResp, MempoolTXResp, NetSpaceResp,
-    NetworkInfoResp, SignagePointOrEOSResp, TXResp, UnfinishedBlockAryResp,
+use crate::clients::error::ChiaRpcError;
+use crate::clients::range::{empty_range, fetch_heights, fetch_range, PartialRange, RangeOptions};
+use crate::clients::requests::fullnode::{
+    GetAdditionsAndRemovals, GetAllMempoolItems, GetAllMempoolTxIds, GetBlock, GetBlockRecord,
+    GetBlockRecordByHeight, GetBlockRecords, GetBlockchainState, GetBlocks, GetCoinRecordByName,
+    GetCoinRecordsByParentIds, GetCoinRecordsByPuzzleHash, GetCoinRecordsByPuzzleHashes,
+    GetInitialFreezePeriod, GetMempoolItemByTxId, GetNetworkInfo, GetNetworkSpace,
+    GetPuzzleAndSolution, GetRecentSignagePointOrEos, GetUnfinishedBlockHeaders, PushTx,
 };
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{BlockchainStateResp, NetworkInfoResp, SignagePointOrEOSResp};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::{ReqwestTransport, RpcTransport};
+
+/// Blocks per `get_blocks` request by default, the node builds the whole response in memory and
+/// full blocks can be megabytes each.
+const BLOCKS_CHUNK_SIZE: u32 = 32;
+/// Block records per `get_block_records` request by default, records are a few hundred bytes.
+const BLOCK_RECORDS_CHUNK_SIZE: u32 = 500;
 
 pub struct FullnodeClient {
-    client: Client,
-    host: String,
-    port: u32,
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl ServiceClient for FullnodeClient {
+    const SERVICE: ChiaService = ChiaService::FullNode;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        FullnodeClient { transport }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        &self.transport
+    }
 }
 
+client_methods!(FullnodeClient);
+
 impl FullnodeClient {
+    #[deprecated(
+        note = "falls back to a client without certificates when they fail to load, use try_new"
+    )]
     pub fn new(host: &str, port: u32, ssl_path: &str) -> Self {
-        FullnodeClient {
-            client: get_client(ssl_path).unwrap_or_default(),
-            host: host.to_string(),
-            port,
-        }
+        Self::try_new(host, port, ssl_path).unwrap_or_else(|_| {
+            Self::from_transport(Arc::new(ReqwestTransport::new(
+                reqwest::Client::default(),
+                get_base_url("https", host, port),
+            )))
+        })
     }
-    pub async fn get_blockchain_state(&self) -> Result<BlockchainState, Error> {
-        Ok(post::<BlockchainStateResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_blockchain_state"),
-            &Map::new(),
-        )
-        .await?
-        .blockchain_state)
-    }
-    pub async fn get_block(&self, header_hash: &Bytes32) -> Result<FullBlock, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("header_hash".to_string(), json!(header_hash));
-        Ok(post::<FullBlockResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_block"),
-            &request_body,
-        )
-        .await?
-        .block)
+    pub async fn get_blockchain_state(&self) -> Result<BlockchainState, ChiaRpcError> {
+        Ok(self.get_blockchain_state_full().await?.blockchain_state)
+    }
+    pub async fn get_blockchain_state_full(&self) -> Result<BlockchainStateResp, ChiaRpcError> {
+        request(self.transport.as_ref(), &GetBlockchainState {}).await
+    }
+    pub async fn get_block(&self, header_hash: &Bytes32) -> Result<FullBlock, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetBlock { header_hash })
+            .await?
+            .block)
     }
     pub async fn get_blocks(
         &self,
         start: u32,
         end: u32,
         exclude_header_hash: bool,
-    ) -> Result<Vec<FullBlock>, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("start".to_string(), json!(start));
-        request_body.insert("end".to_string(), json!(end));
-        request_body.insert(
-            "exclude_header_hash".to_string(),
-            json!(if exclude_header_hash { "True" } else { "False" }),
-        );
-        Ok(post::<FullBlockAryResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_blocks"),
-            &request_body,
-        )
-        .await?
-        .blocks)
+    ) -> Result<Vec<FullBlock>, ChiaRpcError> {
+        let data = GetBlocks {
+            start,
+            end,
+            exclude_header_hash,
+            exclude_reorged: false,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.blocks)
     }
-    pub async fn get_all_blocks(&self, start: u32, end: u32) -> Result<Vec<FullBlock>, Error> {
+    pub async fn get_all_blocks(
+        &self,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<FullBlock>, ChiaRpcError> {
         self.get_blocks(start, end, true).await
     }
-    pub async fn get_block_record_by_height(&self, height: u32) -> Result<BlockRecord, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("height".to_string(), json!(height));
-        Ok(post::<BlockRecordResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_block_record_by_height"),
-            &request_body,
+    /// Fetches the blocks from `start` up to but excluding `end` in chunks, several at once,
+    /// and returns them in height order. Orphaned blocks are left out and a chunk that does not
+    /// return exactly one block per height, ie one past the peak, fails.
+    pub async fn get_blocks_range(
+        &self,
+        start: u32,
+        end: u32,
+        options: &RangeOptions,
+    ) -> Result<Vec<FullBlock>, PartialRange<FullBlock>> {
+        self.resume_blocks_range(empty_range(start, end), options)
+            .await
+    }
+    /// Fetches the heights a failed [`Self::get_blocks_range`] is missing.
+    pub async fn resume_blocks_range(
+        &self,
+        partial: PartialRange<FullBlock>,
+        options: &RangeOptions,
+    ) -> Result<Vec<FullBlock>, PartialRange<FullBlock>> {
+        fetch_range(
+            partial,
+            GetBlocks::ENDPOINT,
+            BLOCKS_CHUNK_SIZE,
+            options,
+            |start, end| {
+                let data = GetBlocks {
+                    start,
+                    end,
+                    exclude_header_hash: true,
+                    exclude_reorged: true,
+                };
+                async move {
+                    fetch_heights(
+                        self.transport.as_ref(),
+                        &data,
+                        "blocks",
+                        "/reward_chain_block/height",
+                    )
+                    .await
+                }
+            },
         )
-        .await?
-        .block_record)
-    }
-    pub async fn get_block_record(&self, header_hash: &Bytes32) -> Result<BlockRecord, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("header_hash".to_string(), json!(header_hash));
-        Ok(post::<BlockRecordResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_block_record"),
-            &request_body,
+        .await
+    }
+    pub async fn get_block_record_by_height(
+        &self,
+        height: u32,
+    ) -> Result<BlockRecord, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetBlockRecordByHeight { height })
+                .await?
+                .block_record,
         )
-        .await?
-        .block_record)
-    }
-    pub async fn get_block_records(&self, start: u32, end: u32) -> Result<Vec<BlockRecord>, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("start".to_string(), json!(start));
-        request_body.insert("end".to_string(), json!(end));
-        Ok(post::<BlockRecordAryResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_block_records"),
-            &request_body,
+    }
+    pub async fn get_block_record(
+        &self,
+        header_hash: &Bytes32,
+    ) -> Result<BlockRecord, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetBlockRecord { header_hash })
+                .await?
+                .block_record,
         )
-        .await?
-        .block_records)
-    }
-    pub async fn get_unfinished_block_headers(&self) -> Result<Vec<UnfinishedBlock>, Error> {
-        Ok(post::<UnfinishedBlockAryResp>(
-            &self.client,
-            &get_url(
-                self.host.as_str(),
-                self.port,
-                "get_unfinished_block_headers",
-            ),
-            &Map::new(),
+    }
+    pub async fn get_block_records(
+        &self,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<BlockRecord>, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetBlockRecords { start, end })
+                .await?
+                .block_records,
+        )
+    }
+    /// Fetches the block records from `start` up to but excluding `end` in chunks, several at
+    /// once, and returns them in height order. A chunk that does not return exactly one record
+    /// per height, ie one past the peak, fails.
+    pub async fn get_block_records_range(
+        &self,
+        start: u32,
+        end: u32,
+        options: &RangeOptions,
+    ) -> Result<Vec<BlockRecord>, PartialRange<BlockRecord>> {
+        self.resume_block_records_range(empty_range(start, end), options)
+            .await
+    }
+    /// Fetches the heights a failed [`Self::get_block_records_range`] is missing.
+    pub async fn resume_block_records_range(
+        &self,
+        partial: PartialRange<BlockRecord>,
+        options: &RangeOptions,
+    ) -> Result<Vec<BlockRecord>, PartialRange<BlockRecord>> {
+        fetch_range(
+            partial,
+            GetBlockRecords::ENDPOINT,
+            BLOCK_RECORDS_CHUNK_SIZE,
+            options,
+            |start, end| {
+                let data = GetBlockRecords { start, end };
+                async move {
+                    fetch_heights(self.transport.as_ref(), &data, "block_records", "/height").await
+                }
+            },
+        )
+        .await
+    }
+    pub async fn get_unfinished_block_headers(&self) -> Result<Vec<UnfinishedBlock>, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetUnfinishedBlockHeaders {})
+                .await?
+                .headers,
         )
-        .await?
-        .headers)
     }
     pub async fn get_network_space(
         &self,
         older_block_header_hash: &Bytes32,
         newer_block_header_hash: &Bytes32,
-    ) -> Result<u64, Error> {
-        let mut request_body = Map::new();
-        request_body.insert(
-            "older_block_header_hash".to_string(),
-            json!(older_block_header_hash),
-        );
-        request_body.insert(
-            "newer_block_header_hash".to_string(),
-            json!(newer_block_header_hash),
-        );
-        Ok(post::<NetSpaceResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_network_space"),
-            &request_body,
-        )
-        .await?
-        .space)
+    ) -> Result<u64, ChiaRpcError> {
+        let data = GetNetworkSpace {
+            older_block_header_hash,
+            newer_block_header_hash,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.space)
     }
     pub async fn get_network_space_by_height(
         &self,
         older_block_height: u32,
         newer_block_height: u32,
-    ) -> Result<u64, Error> {
+    ) -> Result<u64, ChiaRpcError> {
         let older_block = self.get_block_record_by_height(older_block_height).await?;
         let newer_block = self.get_block_record_by_height(newer_block_height).await?;
         self.get_network_space(&older_block.header_hash, &newer_block.header_hash)
@@ -163,62 +232,37 @@ impl FullnodeClient {
     pub async fn get_additions_and_removals(
         &self,
         header_hash: &Bytes32,
-    ) -> Result<(Vec<CoinRecord>, Vec<CoinRecord>), Error> {
-        let mut request_body = Map::new();
-        request_body.insert("header_hash".to_string(), json!(header_hash));
-        let resp = post::<AdditionsAndRemovalsResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_additions_and_removals"),
-            &request_body,
+    ) -> Result<(Vec<CoinRecord>, Vec<CoinRecord>), ChiaRpcError> {
+        let resp = request(
+            self.transport.as_ref(),
+            &GetAdditionsAndRemovals { header_hash },
         )
         .await?;
         Ok((resp.additions, resp.removals))
     }
-    pub async fn get_initial_freeze_period(&self) -> Result<u64, Error> {
-        Ok(post::<InitialFreezePeriodResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_initial_freeze_period"),
-            &Map::new(),
-        )
-        .await?
-        .initial_freeze_end_timestamp)
+    pub async fn get_initial_freeze_period(&self) -> Result<u64, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetInitialFreezePeriod {})
+            .await?
+            .initial_freeze_end_timestamp)
     }
-    pub async fn get_network_info(&self) -> Result<NetworkInfo, Error> {
-        let resp = post::<NetworkInfoResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_network_info"),
-            &Map::new(),
-        )
-        .await?;
+    pub async fn get_network_info(&self) -> Result<NetworkInfo, ChiaRpcError> {
+        let resp = self.get_network_info_full().await?;
         Ok(NetworkInfo {
             network_name: resp.network_name,
             network_prefix: resp.network_prefix,
         })
     }
+    pub async fn get_network_info_full(&self) -> Result<NetworkInfoResp, ChiaRpcError> {
+        request(self.transport.as_ref(), &GetNetworkInfo {}).await
+    }
     pub async fn get_recent_signage_point_or_eos(
         &self,
         sp_hash: Option<&Bytes32>,
         challenge_hash: Option<&Bytes32>,
-    ) -> Result<SignagePointOrEOS, Error> {
-        let mut request_body = Map::new();
-        if sp_hash != None {
-            request_body.insert("sp_hash".to_string(), json!(sp_hash));
-        } else if challenge_hash != None {
-            request_body.insert("challenge_hash".to_string(), json!(challenge_hash));
-        }
-        if sp_hash != None && challenge_hash != None {
-            return Err(Error::new(ErrorKind::InvalidInput, "InvalidArgument get_recent_signage_point_or_eos: One of sp_hash or challenge_hash must be None"));
-        }
-        let resp = post::<SignagePointOrEOSResp>(
-            &self.client,
-            &get_url(
-                self.host.as_str(),
-                self.port,
-                "get_recent_signage_point_or_eos",
-            ),
-            &request_body,
-        )
-        .await?;
+    ) -> Result<SignagePointOrEOS, ChiaRpcError> {
+        let resp = self
+            .get_recent_signage_point_or_eos_full(sp_hash, challenge_hash)
+            .await?;
         Ok(SignagePointOrEOS {
             signage_point: resp.signage_point,
             eos: resp.eos,
@@ -226,32 +270,37 @@ impl FullnodeClient {
             reverted: resp.reverted,
         })
     }
+    pub async fn get_recent_signage_point_or_eos_full(
+        &self,
+        sp_hash: Option<&Bytes32>,
+        challenge_hash: Option<&Bytes32>,
+    ) -> Result<SignagePointOrEOSResp, ChiaRpcError> {
+        if sp_hash.is_some() && challenge_hash.is_some() {
+            return Err(ChiaRpcError::InvalidInput(
+                "get_recent_signage_point_or_eos: One of sp_hash or challenge_hash must be None"
+                    .to_string(),
+            ));
+        }
+        let data = GetRecentSignagePointOrEos {
+            sp_hash,
+            challenge_hash,
+        };
+        request(self.transport.as_ref(), &data).await
+    }
     pub async fn get_coin_records_by_puzzle_hash(
         &self,
         puzzle_hash: &Bytes32,
         include_spent_coins: bool,
         start_height: u32,
         end_height: u32,
-    ) -> Result<Vec<CoinRecord>, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("puzzle_hash".to_string(), json!(puzzle_hash));
-        request_body.insert(
-            "include_spent_coins".to_string(),
-            json!(include_spent_coins),
-        );
-        request_body.insert("start_height".to_string(), json!(start_height));
-        request_body.insert("end_height".to_string(), json!(end_height));
-        Ok(post::<CoinRecordAryResp>(
-            &self.client,
-            &get_url(
-                self.host.as_str(),
-                self.port,
-                "get_coin_records_by_puzzle_hash",
-            ),
-            &request_body,
-        )
-        .await?
-        .coin_records)
+    ) -> Result<Vec<CoinRecord>, ChiaRpcError> {
+        let data = GetCoinRecordsByPuzzleHash {
+            puzzle_hash,
+            include_spent_coins,
+            start_height,
+            end_height,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.coin_records)
     }
     pub async fn get_coin_records_by_puzzle_hashes(
         &self,
@@ -259,41 +308,25 @@ impl FullnodeClient {
         include_spent_coins: bool,
         start_height: u32,
         end_height: u32,
-    ) -> Result<Vec<CoinRecord>, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("puzzle_hashes".to_string(), json!(puzzle_hashes));
-        request_body.insert(
-            "include_spent_coins".to_string(),
-            json!(include_spent_coins),
-        );
-        request_body.insert("start_height".to_string(), json!(start_height));
-        request_body.insert("end_height".to_string(), json!(end_height));
-        Ok(post::<CoinRecordAryResp>(
-            &self.client,
-            &get_url(
-                self.host.as_str(),
-                self.port,
-                "get_coin_records_by_puzzle_hashes",
-            ),
-            &request_body,
-        )
-        .await?
-        .coin_records)
+    ) -> Result<Vec<CoinRecord>, ChiaRpcError> {
+        let data = GetCoinRecordsByPuzzleHashes {
+            puzzle_hashes,
+            include_spent_coins,
+            start_height,
+            end_height,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.coin_records)
     }
 
     pub async fn get_coin_record_by_name(
         &self,
         name: &Bytes32,
-    ) -> Result<Option<CoinRecord>, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("name".to_string(), json!(name));
-        Ok(post::<CoinRecordResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_coin_record_by_name"),
-            &request_body,
+    ) -> Result<Option<CoinRecord>, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetCoinRecordByName { name })
+                .await?
+                .coin_record,
         )
-        .await?
-        .coin_record)
     }
     pub async fn get_coin_records_by_parent_ids(
         &self,
@@ -301,81 +334,59 @@ impl FullnodeClient {
         include_spent_coins: bool,
         start_height: u32,
         end_height: u32,
-    ) -> Result<Vec<CoinRecord>, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("parent_ids".to_string(), json!(parent_ids));
-        request_body.insert(
-            "include_spent_coins".to_string(),
-            json!(include_spent_coins),
-        );
-        request_body.insert("start_height".to_string(), json!(start_height));
-        request_body.insert("end_height".to_string(), json!(end_height));
-        Ok(post::<CoinRecordAryResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_block"),
-            &request_body,
-        )
-        .await?
-        .coin_records)
-    }
-    pub async fn push_tx(&self, spend_bundle: &SpendBundle) -> Result<TXStatus, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("spend_bundle".to_string(), json!(spend_bundle));
-        Ok(post::<TXResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "push_tx"),
-            &request_body,
-        )
-        .await?
-        .status)
+    ) -> Result<Vec<CoinRecord>, ChiaRpcError> {
+        let data = GetCoinRecordsByParentIds {
+            parent_ids,
+            include_spent_coins,
+            start_height,
+            end_height,
+        };
+        Ok(request(self.transport.as_ref(), &data).await?.coin_records)
+    }
+    pub async fn push_tx(&self, spend_bundle: &SpendBundle) -> Result<TXStatus, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &PushTx { spend_bundle })
+            .await?
+            .status)
     }
     pub async fn get_puzzle_and_solution(
         &self,
         coin_id: &Bytes32,
         height: u32,
-    ) -> Result<CoinSpend, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("coin_id".to_string(), json!(coin_id));
-        request_body.insert("height".to_string(), json!(height));
-        Ok(post::<CoinSpendResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_puzzle_and_solution"),
-            &request_body,
+    ) -> Result<CoinSpend, ChiaRpcError> {
+        Ok(request(
+            self.transport.as_ref(),
+            &GetPuzzleAndSolution { coin_id, height },
         )
         .await?
         .coin_solution)
     }
-    pub async fn get_coin_spend(&self, coin_record: &CoinRecord) -> Result<CoinSpend, Error> {
+    pub async fn get_coin_spend(
+        &self,
+        coin_record: &CoinRecord,
+    ) -> Result<CoinSpend, ChiaRpcError> {
         self.get_puzzle_and_solution(&coin_record.coin.name(), coin_record.spent_block_index)
             .await
     }
-    pub async fn get_all_mempool_tx_ids(&self) -> Result<Vec<String>, Error> {
-        Ok(post::<MempoolTXResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_all_mempool_tx_ids"),
-            &Map::new(),
-        )
-        .await?
-        .tx_ids)
+    pub async fn get_all_mempool_tx_ids(&self) -> Result<Vec<String>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetAllMempoolTxIds {})
+            .await?
+            .tx_ids)
     }
-    pub async fn get_all_mempool_items(&self) -> Result<HashMap<String, MemPoolItem>, Error> {
-        Ok(post::<MempoolItemsResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_all_mempool_items"),
-            &Map::new(),
-        )
-        .await?
-        .mempool_items)
-    }
-    pub async fn get_mempool_item_by_tx_id(&self, tx_id: &str) -> Result<MemPoolItem, Error> {
-        let mut request_body = Map::new();
-        request_body.insert("tx_id".to_string(), json!(tx_id));
-        Ok(post::<MempoolItemResp>(
-            &self.client,
-            &get_url(self.host.as_str(), self.port, "get_mempool_item_by_tx_id"),
-            &request_body,
+    pub async fn get_all_mempool_items(
+        &self,
+    ) -> Result<HashMap<String, MempoolItem>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetAllMempoolItems {})
+            .await?
+            .mempool_items)
+    }
+    pub async fn get_mempool_item_by_tx_id(
+        &self,
+        tx_id: &str,
+    ) -> Result<MempoolItem, ChiaRpcError> {
+        Ok(
+            request(self.transport.as_ref(), &GetMempoolItemByTxId { tx_id })
+                .await?
+                .mempool_item,
         )
-        .await?
-        .mempool_item)
     }
 }
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::clients::builder::{client_methods, ServiceClient};
+use crate::clients::common::*;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::harvester::{
+    AddPlotDirectory, DeletePlot, GetHarvesterConfig, GetPlotDirectories, GetPlots, RefreshPlots,
+    RemovePlotDirectory, UpdateHarvesterConfig,
+};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+use crate::clients::types::harvester::{HarvesterConfig, HarvesterPlots, PlotIssues};
+
+pub struct HarvesterClient {
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl ServiceClient for HarvesterClient {
+    const SERVICE: ChiaService = ChiaService::Harvester;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        HarvesterClient { transport }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        &self.transport
+    }
+}
+
+client_methods!(HarvesterClient);
+
+impl HarvesterClient {
+    pub async fn get_plots(&self) -> Result<HarvesterPlots, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetPlots {}).await?.plots)
+    }
+    /// The plots that failed to open, lack keys or share a plot id with another loaded plot.
+    pub async fn get_plot_issues(&self) -> Result<PlotIssues, ChiaRpcError> {
+        Ok(PlotIssues::from_plots(&self.get_plots().await?))
+    }
+    pub async fn refresh_plots(&self) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &RefreshPlots {}).await?;
+        Ok(())
+    }
+    /// Deletes the plot file from disk, not only from the harvester.
+    pub async fn delete_plot(&self, filename: &str) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &DeletePlot { filename }).await?;
+        Ok(())
+    }
+    pub async fn add_plot_directory(&self, dirname: &str) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &AddPlotDirectory { dirname }).await?;
+        Ok(())
+    }
+    pub async fn get_plot_directories(&self) -> Result<Vec<String>, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetPlotDirectories {})
+            .await?
+            .directories)
+    }
+    pub async fn remove_plot_directory(&self, dirname: &str) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &RemovePlotDirectory { dirname }).await?;
+        Ok(())
+    }
+    pub async fn get_harvester_config(&self) -> Result<HarvesterConfig, ChiaRpcError> {
+        Ok(request(self.transport.as_ref(), &GetHarvesterConfig {})
+            .await?
+            .config)
+    }
+    /// Writes the fields that are set to the harvester's config, most take effect on restart.
+    pub async fn update_harvester_config(
+        &self,
+        config: &HarvesterConfig,
+    ) -> Result<(), ChiaRpcError> {
+        request(self.transport.as_ref(), &UpdateHarvesterConfig { config }).await?;
+        Ok(())
+    }
+}
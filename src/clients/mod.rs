@@ -1,4 +1,26 @@
+pub mod builder;
+pub mod cache;
+pub mod cassette;
 mod common;
+pub mod config;
+pub mod crawler;
+pub mod daemon;
+pub mod data_layer;
+pub mod error;
+pub mod events;
+pub mod farmer;
 pub mod fullnode;
-mod responses;
+pub mod harvester;
+pub mod pool;
+pub mod quorum;
+pub mod range;
+pub mod requests;
+pub mod responses;
+pub mod retry;
+pub mod service;
+pub mod simulator;
+pub mod ssl;
+pub mod timelord;
+pub mod transport;
+pub mod types;
 pub mod wallet;
@@ -0,0 +1,422 @@
+use crate::clients::builder::ServiceClient;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::transport::RpcTransport;
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use serde_json::{json, Value};
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Last known state of a node, from `get_blockchain_state` and the calls routed to it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NodeHealth {
+    /// `false` after a health check or call failed to reach the node, until the next check.
+    pub reachable: bool,
+    pub synced: bool,
+    pub peak_height: Option<u32>,
+    pub last_error: Option<String>,
+    pub checked_at: Option<Instant>,
+}
+
+struct Node {
+    name: String,
+    transport: Arc<dyn RpcTransport>,
+    health: Mutex<NodeHealth>,
+}
+
+/// Several full nodes behind one transport, use [`NodePool::client`] to get a
+/// [`crate::clients::fullnode::FullnodeClient`] in front of them.
+///
+/// Calls go to the reachable, synced node with the highest peak, rotating between equally good
+/// nodes, and fail over to the next node when one cannot be reached. Such a node is ranked last
+/// until it answers a call or a health check again. `push_tx` is sent to every node and the
+/// results are merged, see [`NodePool::broadcast`].
+pub struct NodePool {
+    nodes: Vec<Node>,
+    broadcast_endpoints: Vec<String>,
+    next: AtomicUsize,
+}
+
+impl Default for NodePool {
+    fn default() -> Self {
+        NodePool {
+            nodes: vec![],
+            broadcast_endpoints: vec!["push_tx".to_string()],
+            next: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl NodePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a node, nodes start as reachable until a health check says otherwise.
+    pub fn with_node(mut self, name: &str, transport: Arc<dyn RpcTransport>) -> Self {
+        self.nodes.push(Node {
+            name: name.to_string(),
+            transport,
+            health: Mutex::new(NodeHealth {
+                reachable: true,
+                ..NodeHealth::default()
+            }),
+        });
+        self
+    }
+    /// Adds the node an existing client talks to, keeping its TLS and retry settings.
+    pub fn with_client<C: ServiceClient>(self, name: &str, client: &C) -> Self {
+        self.with_node(name, client.transport().clone())
+    }
+    /// Also sends `endpoint` to every node instead of one, in addition to `push_tx`.
+    pub fn broadcast_endpoint(mut self, endpoint: &str) -> Self {
+        self.broadcast_endpoints.push(endpoint.to_string());
+        self
+    }
+    pub fn client<C: ServiceClient>(self: &Arc<Self>) -> C {
+        C::from_transport(self.clone())
+    }
+    /// Name and last known health of every node, in the order they were added.
+    pub fn health(&self) -> Vec<(String, NodeHealth)> {
+        self.nodes
+            .iter()
+            .map(|node| (node.name.clone(), node.health.lock().unwrap().clone()))
+            .collect()
+    }
+    /// Calls `get_blockchain_state` on every node at once and records whether it is synced and
+    /// its peak height.
+    pub async fn check_health(&self) {
+        join_all(self.nodes.iter().map(|node| async move {
+            let result = node
+                .transport
+                .send("get_blockchain_state", &json!({}))
+                .await;
+            let mut health = node.health.lock().unwrap();
+            health.checked_at = Some(Instant::now());
+            match result {
+                Ok(value) if value["success"] != Value::Bool(false) => {
+                    let state = &value["blockchain_state"];
+                    health.reachable = true;
+                    health.synced = state["sync"]["synced"].as_bool().unwrap_or(false);
+                    health.peak_height = state["peak"]["height"].as_u64().map(|h| h as u32);
+                    health.last_error = None;
+                }
+                Ok(value) => {
+                    health.reachable = true;
+                    health.synced = false;
+                    let error = value["error"].as_str().map(str::to_string);
+                    health.last_error =
+                        error.or_else(|| Some("get_blockchain_state failed".to_string()));
+                }
+                Err(e) => {
+                    health.reachable = false;
+                    health.last_error = Some(e.to_string());
+                }
+            }
+        }))
+        .await;
+    }
+    /// Runs [`Self::check_health`] every `interval` until the returned task is aborted.
+    pub fn spawn_health_checks(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let pool = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while let Some(pool) = pool.upgrade() {
+                pool.check_health().await;
+                drop(pool);
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+    /// Sends the call to every node at once and returns each node's result, in node order.
+    pub async fn broadcast(
+        &self,
+        endpoint: &str,
+        body: &Value,
+    ) -> Vec<(String, Result<Value, ChiaRpcError>)> {
+        join_all(self.nodes.iter().map(|node| async move {
+            (node.name.clone(), node.transport.send(endpoint, body).await)
+        }))
+        .await
+    }
+    /// Node indexes from best to worst: reachable, then synced, then highest peak.
+    fn ranked(&self) -> Vec<usize> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let len = self.nodes.len();
+        let mut order: Vec<usize> = (0..len).map(|i| (start + i) % len).collect();
+        let healths: Vec<NodeHealth> = self
+            .nodes
+            .iter()
+            .map(|node| node.health.lock().unwrap().clone())
+            .collect();
+        order.sort_by_key(|i| {
+            let health = &healths[*i];
+            Reverse((health.reachable, health.synced, health.peak_height))
+        });
+        order
+    }
+    fn mark_unreachable(&self, index: usize, error: &ChiaRpcError) {
+        let mut health = self.nodes[index].health.lock().unwrap();
+        health.reachable = false;
+        health.last_error = Some(error.to_string());
+    }
+}
+
+#[async_trait]
+impl RpcTransport for NodePool {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        if self.broadcast_endpoints.iter().any(|e| e == endpoint) {
+            return merge_broadcast(self.broadcast(endpoint, body).await);
+        }
+        let mut last_error = None;
+        for index in self.ranked() {
+            match self.nodes[index].transport.send(endpoint, body).await {
+                Err(e) if failover(&e) => {
+                    self.mark_unreachable(index, &e);
+                    last_error = Some(e);
+                }
+                result => {
+                    self.nodes[index].health.lock().unwrap().reachable = true;
+                    return result;
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(empty_pool))
+    }
+}
+
+/// Errors that mean the node could not answer, as opposed to the node rejecting the call. A 4xx
+/// status would be the same on every node.
+fn failover(error: &ChiaRpcError) -> bool {
+    matches!(
+        error,
+        ChiaRpcError::Transport(_) | ChiaRpcError::Unreachable(_) | ChiaRpcError::Timeout(_)
+    ) || error.is_retryable()
+}
+
+/// Picks the most successful response of a broadcast `push_tx`: a `SUCCESS` status from any
+/// node wins over `PENDING`, which wins over any other response. The error of the first node is
+/// returned only when no node answered.
+fn merge_broadcast(
+    results: Vec<(String, Result<Value, ChiaRpcError>)>,
+) -> Result<Value, ChiaRpcError> {
+    let rank = |value: &Value| match value["status"].as_str() {
+        _ if value["success"] == Value::Bool(false) => 0,
+        Some("SUCCESS") => 3,
+        Some("PENDING") => 2,
+        _ => 1,
+    };
+    let mut best: Option<Value> = None;
+    let mut first_error = None;
+    for (_, result) in results {
+        match result {
+            Ok(value) => {
+                if best.as_ref().is_none_or(|best| rank(&value) > rank(best)) {
+                    best = Some(value);
+                }
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    match (best, first_error) {
+        (Some(value), _) => Ok(value),
+        (None, Some(e)) => Err(e),
+        (None, None) => Err(empty_pool()),
+    }
+}
+
+fn empty_pool() -> ChiaRpcError {
+    ChiaRpcError::Config("The node pool is empty".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::transport::MockTransport;
+    use reqwest::StatusCode;
+
+    fn pool(nodes: &[&Arc<MockTransport>]) -> NodePool {
+        nodes
+            .iter()
+            .enumerate()
+            .fold(NodePool::new(), |pool, (i, node)| {
+                pool.with_node(&format!("node{}", i), (*node).clone())
+            })
+    }
+
+    fn node() -> Arc<MockTransport> {
+        let node = Arc::new(MockTransport::new());
+        node.respond("get_routes", json!({ "success": true, "routes": [] }));
+        node
+    }
+
+    fn state(synced: bool, height: u32) -> Value {
+        json!({
+            "success": true,
+            "blockchain_state": { "sync": { "synced": synced }, "peak": { "height": height } },
+        })
+    }
+
+    fn calls(node: &MockTransport) -> usize {
+        node.requests_to("get_routes").len()
+    }
+
+    #[tokio::test]
+    async fn fails_over_when_a_node_cannot_answer() {
+        let (a, b, c) = (node(), node(), node());
+        a.fail_once("get_routes", ChiaRpcError::Unreachable("a".to_string()));
+        b.fail_once(
+            "get_routes",
+            ChiaRpcError::Timeout("get_routes".to_string()),
+        );
+        let pool = pool(&[&a, &b, &c]);
+        pool.send("get_routes", &json!({})).await.unwrap();
+        assert_eq!((calls(&a), calls(&b), calls(&c)), (1, 1, 1));
+        let health = pool.health();
+        assert!(!health[0].1.reachable);
+        assert!(!health[1].1.reachable);
+        assert!(health[2].1.reachable);
+        pool.send("get_routes", &json!({})).await.unwrap();
+        assert_eq!((calls(&a), calls(&b), calls(&c)), (1, 1, 2));
+    }
+
+    #[tokio::test]
+    async fn client_errors_do_not_fail_over() {
+        let (a, b) = (node(), node());
+        a.fail_once(
+            "get_routes",
+            ChiaRpcError::HttpStatus {
+                url: "get_routes".to_string(),
+                status: StatusCode::BAD_REQUEST,
+                body: String::new(),
+            },
+        );
+        let pool = pool(&[&a, &b]);
+        let error = pool.send("get_routes", &json!({})).await.unwrap_err();
+        assert!(matches!(
+            error,
+            ChiaRpcError::HttpStatus {
+                status: StatusCode::BAD_REQUEST,
+                ..
+            }
+        ));
+        assert_eq!((calls(&a), calls(&b)), (1, 0));
+        assert!(pool.health()[0].1.reachable);
+    }
+
+    #[tokio::test]
+    async fn unsynced_and_stale_nodes_are_ranked_last() {
+        let (a, b, c) = (node(), node(), node());
+        a.respond("get_blockchain_state", state(false, 120));
+        b.respond("get_blockchain_state", state(true, 90));
+        c.respond("get_blockchain_state", state(true, 100));
+        let pool = pool(&[&a, &b, &c]);
+        pool.check_health().await;
+        assert_eq!(pool.ranked(), vec![2, 1, 0]);
+        assert_eq!(pool.ranked(), vec![2, 1, 0]);
+        pool.send("get_routes", &json!({})).await.unwrap();
+        assert_eq!((calls(&a), calls(&b), calls(&c)), (0, 0, 1));
+    }
+
+    #[tokio::test]
+    async fn equal_nodes_take_turns() {
+        let (a, b) = (node(), node());
+        let pool = pool(&[&a, &b]);
+        for _ in 0..4 {
+            pool.send("get_routes", &json!({})).await.unwrap();
+        }
+        assert_eq!((calls(&a), calls(&b)), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn health_check_refreshes_a_failed_node() {
+        let (a, b) = (node(), node());
+        a.fail_once("get_routes", ChiaRpcError::Unreachable("a".to_string()));
+        a.respond("get_blockchain_state", state(true, 100));
+        b.fail_once(
+            "get_blockchain_state",
+            ChiaRpcError::Unreachable("b".to_string()),
+        );
+        let pool = pool(&[&a, &b]);
+        pool.send("get_routes", &json!({})).await.unwrap();
+        assert!(!pool.health()[0].1.reachable);
+        pool.check_health().await;
+        let health = pool.health();
+        assert!(health[0].1.reachable && health[0].1.synced);
+        assert_eq!(health[0].1.peak_height, Some(100));
+        assert_eq!(health[0].1.last_error, None);
+        assert!(!health[1].1.reachable);
+        assert!(health[1].1.last_error.is_some());
+        assert!(health[1].1.checked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn health_check_keeps_the_node_error_message() {
+        let (a, b) = (node(), node());
+        a.respond(
+            "get_blockchain_state",
+            json!({ "success": false, "error": "Node not synced" }),
+        );
+        b.respond("get_blockchain_state", json!({ "success": false }));
+        let pool = pool(&[&a, &b]);
+        pool.check_health().await;
+        let health = pool.health();
+        assert_eq!(health[0].1.last_error.as_deref(), Some("Node not synced"));
+        assert_eq!(
+            health[1].1.last_error.as_deref(),
+            Some("get_blockchain_state failed")
+        );
+        assert!(health.iter().all(|(_, h)| h.reachable && !h.synced));
+    }
+
+    #[tokio::test]
+    async fn push_tx_prefers_success_over_pending() {
+        let (a, b) = (node(), node());
+        a.respond("push_tx", json!({ "success": true, "status": "PENDING" }));
+        b.respond("push_tx", json!({ "success": true, "status": "SUCCESS" }));
+        let pool = pool(&[&a, &b]);
+        let value = pool.send("push_tx", &json!({})).await.unwrap();
+        assert_eq!(value["status"], "SUCCESS");
+        assert_eq!(a.requests_to("push_tx").len(), 1);
+        assert_eq!(b.requests_to("push_tx").len(), 1);
+    }
+
+    #[test]
+    fn merge_broadcast_ranks_rejections_above_errors() {
+        let unreachable = || Err(ChiaRpcError::Unreachable("a".to_string()));
+        let rejected = json!({ "success": false, "error": "DOUBLE_SPEND" });
+        let merged = merge_broadcast(vec![
+            ("a".to_string(), unreachable()),
+            ("b".to_string(), Ok(rejected.clone())),
+        ]);
+        assert_eq!(merged.unwrap(), rejected);
+        let merged = merge_broadcast(vec![
+            ("a".to_string(), Ok(rejected)),
+            (
+                "b".to_string(),
+                Ok(json!({ "success": true, "status": "PENDING" })),
+            ),
+        ]);
+        assert_eq!(merged.unwrap()["status"], "PENDING");
+        let merged = merge_broadcast(vec![
+            ("a".to_string(), unreachable()),
+            (
+                "b".to_string(),
+                Err(ChiaRpcError::Timeout("push_tx".to_string())),
+            ),
+        ]);
+        assert!(matches!(merged, Err(ChiaRpcError::Unreachable(_))));
+    }
+
+    #[tokio::test]
+    async fn empty_pool_is_an_error() {
+        let pool = NodePool::new();
+        for endpoint in ["get_routes", "push_tx"] {
+            let error = pool.send(endpoint, &json!({})).await.unwrap_err();
+            assert!(matches!(error, ChiaRpcError::Config(_)));
+        }
+    }
+}
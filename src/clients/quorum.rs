@@ -0,0 +1,401 @@
+use druid_garden_chia_types::blockchain::block_record::BlockRecord;
+use druid_garden_chia_types::blockchain::coin_record::CoinRecord;
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use futures_util::future::join_all;
+use serde_json::{json, Value};
+
+use crate::clients::common::decode;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::fullnode::FullnodeClient;
+use crate::clients::requests::fullnode::{
+    GetAdditionsAndRemovals, GetBlockRecordByHeight, GetCoinRecordByName,
+};
+use crate::clients::requests::RpcRequest;
+
+/// Outcome of a read sent to several nodes.
+pub enum Quorum<T> {
+    Agreed(Agreement<T>),
+    Diverged(Disagreement),
+}
+
+impl<T> Quorum<T> {
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Quorum<U> {
+        match self {
+            Quorum::Agreed(agreement) => Quorum::Agreed(Agreement {
+                value: f(agreement.value),
+                nodes: agreement.nodes,
+                dissenting: agreement.dissenting,
+                failed: agreement.failed,
+            }),
+            Quorum::Diverged(disagreement) => Quorum::Diverged(disagreement),
+        }
+    }
+    /// The agreed value, or the disagreement as an error.
+    pub fn agreed(self) -> Result<T, Disagreement> {
+        match self {
+            Quorum::Agreed(agreement) => Ok(agreement.value),
+            Quorum::Diverged(disagreement) => Err(disagreement),
+        }
+    }
+}
+
+pub struct Agreement<T> {
+    pub value: T,
+    /// Nodes that returned `value`.
+    pub nodes: Vec<String>,
+    /// Nodes that answered with something else, too few to block the quorum.
+    pub dissenting: Vec<String>,
+    pub failed: Vec<(String, ChiaRpcError)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// Nodes returned different records, ie they follow different forks.
+    Conflict,
+    /// The nodes that know the record agree, but too few of them answered or know it yet. Nodes
+    /// that do not know it form the `null` group, those that did not answer are `failed`.
+    Insufficient,
+}
+
+/// Nodes that returned the same value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseGroup {
+    pub nodes: Vec<String>,
+    /// The compared part of the response, `null` when the node does not know the record.
+    pub value: Value,
+}
+
+#[derive(Debug)]
+pub struct Disagreement {
+    pub endpoint: String,
+    pub kind: DivergenceKind,
+    /// Number of matching answers that was needed.
+    pub required: usize,
+    /// Largest group first.
+    pub groups: Vec<ResponseGroup>,
+    pub failed: Vec<(String, ChiaRpcError)>,
+}
+
+impl std::fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups: Vec<String> = self
+            .groups
+            .iter()
+            .map(|group| group.nodes.join(", "))
+            .collect();
+        write!(
+            f,
+            "{:?} on {}: needed {} matching answers, got [{}] with {} failed",
+            self.kind,
+            self.endpoint,
+            self.required,
+            groups.join("] ["),
+            self.failed.len()
+        )
+    }
+}
+
+/// Sends reads to several full nodes and only accepts a result enough of them agree on, to
+/// check coin states before acting on them.
+///
+/// Responses are compared as JSON, by default a majority of the nodes has to agree. A node
+/// answering that the record is not found counts as an answer of its own, so nodes agreeing a
+/// record does not exist produce `None`, while unreachable nodes are only listed as failed.
+#[derive(Default)]
+pub struct QuorumClient {
+    nodes: Vec<(String, FullnodeClient)>,
+    required: Option<usize>,
+    unanimous: bool,
+}
+
+impl QuorumClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_node(mut self, name: &str, client: FullnodeClient) -> Self {
+        self.nodes.push((name.to_string(), client));
+        self
+    }
+    /// Number of nodes that must return the same value, defaults to a majority.
+    pub fn required(mut self, required: usize) -> Self {
+        self.required = Some(required.max(1));
+        self
+    }
+    /// Requires every node to answer and agree.
+    pub fn unanimous(mut self) -> Self {
+        self.unanimous = true;
+        self
+    }
+    fn required_count(&self) -> usize {
+        match self.required {
+            _ if self.unanimous => self.nodes.len().max(1),
+            Some(required) => required,
+            None => self.nodes.len() / 2 + 1,
+        }
+    }
+    /// `None` agreed on means the nodes agree the coin does not exist.
+    pub async fn get_coin_record_by_name(
+        &self,
+        name: &Bytes32,
+    ) -> Result<Quorum<Option<CoinRecord>>, ChiaRpcError> {
+        Ok(self
+            .quorum(&GetCoinRecordByName { name }, |resp| {
+                resp["coin_record"].clone()
+            })
+            .await?
+            .map(|resp| resp.and_then(|resp| resp.coin_record)))
+    }
+    /// `None` agreed on means the nodes agree the height is past their peak.
+    pub async fn get_block_record_by_height(
+        &self,
+        height: u32,
+    ) -> Result<Quorum<Option<BlockRecord>>, ChiaRpcError> {
+        Ok(self
+            .quorum(&GetBlockRecordByHeight { height }, |resp| {
+                resp["block_record"].clone()
+            })
+            .await?
+            .map(|resp| resp.map(|resp| resp.block_record)))
+    }
+    /// Additions and removals are compared regardless of their order, `None` agreed on means the
+    /// nodes agree the block does not exist.
+    pub async fn get_additions_and_removals(
+        &self,
+        header_hash: &Bytes32,
+    ) -> Result<Quorum<Option<(Vec<CoinRecord>, Vec<CoinRecord>)>>, ChiaRpcError> {
+        Ok(self
+            .quorum(&GetAdditionsAndRemovals { header_hash }, |resp| {
+                json!({
+                    "additions": sorted(&resp["additions"]),
+                    "removals": sorted(&resp["removals"]),
+                })
+            })
+            .await?
+            .map(|resp| resp.map(|resp| (resp.additions, resp.removals))))
+    }
+    /// Sends `data` to every node and groups the answers by the part picked by `select`. Not
+    /// found answers are grouped as `null` and agreed on as `None`.
+    pub async fn quorum<R, F>(
+        &self,
+        data: &R,
+        select: F,
+    ) -> Result<Quorum<Option<R::Response>>, ChiaRpcError>
+    where
+        R: RpcRequest,
+        F: Fn(&Value) -> Value,
+    {
+        let body = serde_json::to_value(data).map_err(|e| {
+            ChiaRpcError::InvalidInput(format!(
+                "Failed to serialize request to {}: {}",
+                R::ENDPOINT,
+                e
+            ))
+        })?;
+        let results = join_all(
+            self.nodes
+                .iter()
+                .map(|(_, client)| client.call_raw(R::ENDPOINT, body.clone())),
+        )
+        .await;
+        let mut groups: Vec<(ResponseGroup, Option<Value>)> = vec![];
+        let mut failed = vec![];
+        for ((name, _), result) in self.nodes.iter().zip(results) {
+            let (value, resp) = match result {
+                Ok(resp) => (select(&resp), Some(resp)),
+                Err(e) if e.is_not_found() => (Value::Null, None),
+                Err(e) => {
+                    failed.push((name.clone(), e));
+                    continue;
+                }
+            };
+            match groups.iter_mut().find(|(group, _)| group.value == value) {
+                Some((group, _)) => group.nodes.push(name.clone()),
+                None => groups.push((
+                    ResponseGroup {
+                        nodes: vec![name.clone()],
+                        value,
+                    },
+                    resp,
+                )),
+            }
+        }
+        groups.sort_by_key(|(group, _)| std::cmp::Reverse(group.nodes.len()));
+        let required = self.required_count();
+        match groups.first() {
+            Some((group, _)) if group.nodes.len() >= required => {
+                let mut groups = groups.into_iter();
+                let (agreed, resp) = groups.next().unwrap();
+                Ok(Quorum::Agreed(Agreement {
+                    value: resp.map(|resp| decode(R::ENDPOINT, resp)).transpose()?,
+                    nodes: agreed.nodes,
+                    dissenting: groups.flat_map(|(group, _)| group.nodes).collect(),
+                    failed,
+                }))
+            }
+            _ => {
+                let known = groups
+                    .iter()
+                    .filter(|(group, _)| !group.value.is_null())
+                    .count();
+                Ok(Quorum::Diverged(Disagreement {
+                    endpoint: R::ENDPOINT.to_string(),
+                    kind: if known > 1 {
+                        DivergenceKind::Conflict
+                    } else {
+                        DivergenceKind::Insufficient
+                    },
+                    required,
+                    groups: groups.into_iter().map(|(group, _)| group).collect(),
+                    failed,
+                }))
+            }
+        }
+    }
+}
+
+fn sorted(values: &Value) -> Value {
+    let mut values = values.as_array().cloned().unwrap_or_default();
+    values.sort_by_key(|value| value.to_string());
+    Value::Array(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::builder::ServiceClient;
+    use crate::clients::requests::fullnode::GetNetworkInfo;
+    use crate::clients::transport::MockTransport;
+    use std::sync::Arc;
+
+    fn coin_id() -> Bytes32 {
+        serde_json::from_value(json!(format!("0x{}", "01".repeat(32)))).unwrap()
+    }
+
+    fn quorum(answers: Vec<Result<Value, ChiaRpcError>>) -> QuorumClient {
+        answers
+            .into_iter()
+            .enumerate()
+            .fold(QuorumClient::new(), |quorum, (i, answer)| {
+                let transport = Arc::new(MockTransport::new());
+                for endpoint in ["get_coin_record_by_name", "get_network_info"] {
+                    match &answer {
+                        Ok(value) => transport.respond(endpoint, value.clone()),
+                        Err(_) => transport
+                            .fail_once(endpoint, ChiaRpcError::Unreachable(format!("node{}", i))),
+                    };
+                }
+                quorum.with_node(
+                    &format!("node{}", i),
+                    FullnodeClient::from_transport(transport),
+                )
+            })
+    }
+
+    fn record(spent: bool) -> Result<Value, ChiaRpcError> {
+        Ok(json!({ "success": true, "coin_record": { "spent": spent } }))
+    }
+
+    fn not_found() -> Result<Value, ChiaRpcError> {
+        Ok(json!({
+            "success": false,
+            "error": format!("Coin record 0x{} not found", "01".repeat(32)),
+        }))
+    }
+
+    fn unreachable() -> Result<Value, ChiaRpcError> {
+        Err(ChiaRpcError::Unreachable(String::new()))
+    }
+
+    fn network(name: &str) -> Result<Value, ChiaRpcError> {
+        Ok(json!({ "success": true, "network_name": name, "network_prefix": "xch" }))
+    }
+
+    fn names(nodes: &[&str]) -> Vec<String> {
+        nodes.iter().map(|node| node.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn majority_agrees() {
+        let quorum = quorum(vec![
+            network("mainnet"),
+            network("testnet11"),
+            network("mainnet"),
+        ]);
+        let result = quorum
+            .quorum(&GetNetworkInfo {}, |resp| resp["network_name"].clone())
+            .await
+            .unwrap();
+        let Quorum::Agreed(agreement) = result else {
+            panic!("expected an agreement");
+        };
+        assert_eq!(agreement.value.unwrap().network_name, "mainnet");
+        assert_eq!(agreement.nodes, names(&["node0", "node2"]));
+        assert_eq!(agreement.dissenting, names(&["node1"]));
+        assert!(agreement.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn different_records_are_a_conflict() {
+        let quorum = quorum(vec![record(false), record(true), not_found()]);
+        let result = quorum.get_coin_record_by_name(&coin_id()).await.unwrap();
+        let Quorum::Diverged(disagreement) = result else {
+            panic!("expected a disagreement");
+        };
+        assert_eq!(disagreement.kind, DivergenceKind::Conflict);
+        assert_eq!(disagreement.endpoint, "get_coin_record_by_name");
+        assert_eq!(disagreement.required, 2);
+        assert_eq!(
+            disagreement.groups,
+            vec![
+                ResponseGroup {
+                    nodes: names(&["node0"]),
+                    value: json!({ "spent": false }),
+                },
+                ResponseGroup {
+                    nodes: names(&["node1"]),
+                    value: json!({ "spent": true }),
+                },
+                ResponseGroup {
+                    nodes: names(&["node2"]),
+                    value: Value::Null,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn too_few_answers_are_insufficient() {
+        let quorum = quorum(vec![record(false), not_found(), unreachable()]);
+        let result = quorum.get_coin_record_by_name(&coin_id()).await.unwrap();
+        let Quorum::Diverged(disagreement) = result else {
+            panic!("expected a disagreement");
+        };
+        assert_eq!(disagreement.kind, DivergenceKind::Insufficient);
+        assert_eq!(disagreement.groups.len(), 2);
+        assert_eq!(disagreement.failed.len(), 1);
+        assert_eq!(disagreement.failed[0].0, "node2");
+    }
+
+    #[tokio::test]
+    async fn not_found_answers_agree_on_none() {
+        let quorum = quorum(vec![not_found(), unreachable(), not_found()]);
+        let result = quorum.get_coin_record_by_name(&coin_id()).await.unwrap();
+        let Quorum::Agreed(agreement) = result else {
+            panic!("expected an agreement");
+        };
+        assert!(agreement.value.is_none());
+        assert_eq!(agreement.nodes, names(&["node0", "node2"]));
+        assert_eq!(agreement.failed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unanimous_needs_every_node() {
+        let quorum = quorum(vec![not_found(), not_found(), unreachable()]).unanimous();
+        let result = quorum.get_coin_record_by_name(&coin_id()).await.unwrap();
+        let Quorum::Diverged(disagreement) = result else {
+            panic!("expected a disagreement");
+        };
+        assert_eq!(disagreement.kind, DivergenceKind::Insufficient);
+        assert_eq!(disagreement.required, 3);
+    }
+}
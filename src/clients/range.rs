@@ -0,0 +1,455 @@
+use crate::clients::common::{call, decode};
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::RpcRequest;
+use crate::clients::transport::RpcTransport;
+use futures_util::future;
+use futures_util::stream::{self, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Reported after every chunk of a range fetch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeProgress {
+    /// Heights of the chunk that just completed.
+    pub chunk: Range<u32>,
+    /// Heights fetched so far, including those of earlier attempts when resuming.
+    pub fetched: u32,
+    pub total: u32,
+}
+
+pub type ProgressCallback = Arc<dyn Fn(&RangeProgress) + Send + Sync>;
+
+/// How [`crate::clients::fullnode::FullnodeClient::get_blocks_range`] and
+/// [`crate::clients::fullnode::FullnodeClient::get_block_records_range`] split and fetch a range.
+///
+/// Chunks default to 32 blocks or 500 block records with 4 requests in flight.
+#[derive(Clone, Default)]
+pub struct RangeOptions {
+    chunk_size: Option<u32>,
+    concurrency: Option<usize>,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl RangeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Heights per request, keep it within the node's limit for the call.
+    pub fn chunk_size(mut self, chunk_size: u32) -> Self {
+        self.chunk_size = Some(chunk_size.max(1));
+        self
+    }
+    /// Maximum number of requests in flight.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency.max(1));
+        self
+    }
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: Fn(&RangeProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+}
+
+/// The chunks fetched before a range fetch failed, pass it to the `resume_*` call to fetch only
+/// the missing heights.
+#[derive(Debug)]
+pub struct PartialRange<T> {
+    pub range: Range<u32>,
+    /// Fetched chunks by their first height.
+    chunks: BTreeMap<u32, (u32, Vec<T>)>,
+    pub error: ChiaRpcError,
+}
+
+impl<T> PartialRange<T> {
+    /// Heights of the range that were not fetched, in order.
+    pub fn missing(&self) -> Vec<Range<u32>> {
+        let mut missing = vec![];
+        let mut height = self.range.start;
+        for (start, (end, _)) in &self.chunks {
+            if *start > height {
+                missing.push(height..*start);
+            }
+            height = height.max(*end);
+        }
+        if height < self.range.end {
+            missing.push(height..self.range.end);
+        }
+        missing
+    }
+    /// The items fetched without a gap from the start of the range, and the height to continue
+    /// from.
+    pub fn into_contiguous(self) -> (Vec<T>, u32) {
+        let mut items = vec![];
+        let mut height = self.range.start;
+        for (start, (end, chunk)) in self.chunks {
+            if start != height {
+                break;
+            }
+            items.extend(chunk);
+            height = end;
+        }
+        (items, height)
+    }
+}
+
+impl<T> Display for PartialRange<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Fetching heights {}..{} stopped with {} missing ranges: {}",
+            self.range.start,
+            self.range.end,
+            self.missing().len(),
+            self.error
+        )
+    }
+}
+
+impl<T: Debug> std::error::Error for PartialRange<T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T> From<PartialRange<T>> for ChiaRpcError {
+    fn from(partial: PartialRange<T>) -> Self {
+        partial.error
+    }
+}
+
+/// Sends a range request and decodes the array under `key` of the response, pairing every item
+/// with the height found at the JSON pointer `height`.
+pub(crate) async fn fetch_heights<R, T>(
+    transport: &dyn RpcTransport,
+    request: &R,
+    key: &str,
+    height: &str,
+) -> Result<Vec<(u32, T)>, ChiaRpcError>
+where
+    R: RpcRequest,
+    T: DeserializeOwned,
+{
+    let mut resp: Value = call(transport, R::ENDPOINT, request).await?;
+    let items = match resp.get_mut(key).map(Value::take) {
+        Some(Value::Array(items)) => items,
+        _ => {
+            return Err(ChiaRpcError::UnexpectedResponse {
+                endpoint: R::ENDPOINT.to_string(),
+                message: format!("Missing array '{}'", key),
+            })
+        }
+    };
+    items
+        .into_iter()
+        .map(|item| {
+            let item_height = item
+                .pointer(height)
+                .and_then(Value::as_u64)
+                .and_then(|h| u32::try_from(h).ok())
+                .ok_or_else(|| ChiaRpcError::UnexpectedResponse {
+                    endpoint: R::ENDPOINT.to_string(),
+                    message: format!("Item without a height at '{}'", height),
+                })?;
+            Ok((item_height, decode(R::ENDPOINT, item)?))
+        })
+        .collect()
+}
+
+/// Sorts the items of a chunk by height, failing unless there is exactly one per height.
+fn check_chunk<T>(
+    endpoint: &str,
+    chunk: &Range<u32>,
+    mut items: Vec<(u32, T)>,
+) -> Result<Vec<T>, ChiaRpcError> {
+    items.sort_by_key(|(height, _)| *height);
+    let exact = items.len() == chunk.len()
+        && items
+            .iter()
+            .zip(chunk.clone())
+            .all(|((height, _), expected)| *height == expected);
+    if !exact {
+        return Err(ChiaRpcError::UnexpectedResponse {
+            endpoint: endpoint.to_string(),
+            message: format!(
+                "Expected one item for each height {}..{}, got {} items",
+                chunk.start,
+                chunk.end,
+                items.len()
+            ),
+        });
+    }
+    Ok(items.into_iter().map(|(_, item)| item).collect())
+}
+
+/// Fetches the missing heights of `partial` in chunks with `fetch`, returning the whole range
+/// in order once every chunk succeeded. A chunk that does not hold exactly one item per height
+/// counts as failed.
+pub(crate) async fn fetch_range<T, F, Fut>(
+    mut partial: PartialRange<T>,
+    endpoint: &str,
+    default_chunk_size: u32,
+    options: &RangeOptions,
+    fetch: F,
+) -> Result<Vec<T>, PartialRange<T>>
+where
+    F: Fn(u32, u32) -> Fut,
+    Fut: Future<Output = Result<Vec<(u32, T)>, ChiaRpcError>>,
+{
+    let chunk_size = options.chunk_size.unwrap_or(default_chunk_size);
+    let chunks: Vec<Range<u32>> = partial
+        .missing()
+        .into_iter()
+        .flat_map(|gap| {
+            (gap.start..gap.end)
+                .step_by(chunk_size as usize)
+                .map(move |start| start..gap.end.min(start.saturating_add(chunk_size)))
+        })
+        .collect();
+    let total = partial.range.end.saturating_sub(partial.range.start);
+    let mut fetched: u32 = partial
+        .chunks
+        .iter()
+        .map(|(start, (end, _))| end - start)
+        .sum();
+    // After a failure no new chunks are started, those in flight still complete and are kept.
+    let stop = AtomicBool::new(false);
+    let mut results = stream::iter(chunks)
+        .take_while(|_| future::ready(!stop.load(Ordering::Relaxed)))
+        .map(|chunk| {
+            let request = fetch(chunk.start, chunk.end);
+            async move { (chunk, request.await) }
+        })
+        .buffer_unordered(options.concurrency.unwrap_or(4));
+    let mut error = None;
+    while let Some((chunk, result)) = results.next().await {
+        match result.and_then(|items| check_chunk(endpoint, &chunk, items)) {
+            Ok(items) => {
+                fetched += chunk.end - chunk.start;
+                if let Some(on_progress) = &options.on_progress {
+                    on_progress(&RangeProgress {
+                        chunk: chunk.clone(),
+                        fetched,
+                        total,
+                    });
+                }
+                partial.chunks.insert(chunk.start, (chunk.end, items));
+            }
+            Err(e) => {
+                stop.store(true, Ordering::Relaxed);
+                error.get_or_insert(e);
+            }
+        }
+    }
+    match error {
+        Some(e) => {
+            partial.error = e;
+            Err(partial)
+        }
+        None => Ok(partial
+            .chunks
+            .into_values()
+            .flat_map(|(_, items)| items)
+            .collect()),
+    }
+}
+
+/// A range with nothing fetched yet, `error` is replaced before it is ever returned.
+pub(crate) fn empty_range<T>(start: u32, end: u32) -> PartialRange<T> {
+    PartialRange {
+        range: start..end.max(start),
+        chunks: BTreeMap::new(),
+        error: ChiaRpcError::InvalidInput("Range not fetched".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::builder::ServiceClient;
+    use crate::clients::fullnode::FullnodeClient;
+    use crate::clients::transport::MockTransport;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn hash() -> String {
+        format!("0x{}", "00".repeat(32))
+    }
+
+    fn record(height: u32) -> Value {
+        json!({
+            "header_hash": hash(),
+            "prev_hash": hash(),
+            "height": height,
+            "weight": 1_000,
+            "total_iters": 1_000_000,
+            "signage_point_index": 3,
+            "challenge_vdf_output": { "data": "0x00" },
+            "infused_challenge_vdf_output": null,
+            "reward_infusion_new_challenge": hash(),
+            "challenge_block_info_hash": hash(),
+            "sub_slot_iters": 147_849_216,
+            "pool_puzzle_hash": hash(),
+            "farmer_puzzle_hash": hash(),
+            "required_iters": 1_024,
+            "deficit": 16,
+            "overflow": false,
+            "prev_transaction_block_height": height.saturating_sub(1),
+            "timestamp": null,
+            "prev_transaction_block_hash": null,
+            "fees": null,
+            "reward_claims_incorporated": null,
+            "finished_challenge_slot_hashes": null,
+            "finished_infused_challenge_slot_hashes": null,
+            "finished_reward_slot_hashes": null,
+            "sub_epoch_summary_included": null,
+        })
+    }
+
+    fn records(heights: Range<u32>) -> Value {
+        json!({ "success": true, "block_records": heights.map(record).collect::<Vec<_>>() })
+    }
+
+    fn partial(range: Range<u32>, chunks: &[(u32, u32)]) -> PartialRange<u32> {
+        PartialRange {
+            range,
+            chunks: chunks
+                .iter()
+                .map(|&(start, end)| (start, (end, (start..end).collect())))
+                .collect(),
+            error: ChiaRpcError::Timeout("get_blocks".to_string()),
+        }
+    }
+
+    #[test]
+    fn missing_lists_the_gaps() {
+        assert_eq!(partial(0..10, &[]).missing(), vec![0..10]);
+        assert_eq!(partial(0..10, &[(0, 10)]).missing(), vec![]);
+        assert_eq!(
+            partial(0..10, &[(2, 4), (6, 8)]).missing(),
+            vec![0..2, 4..6, 8..10]
+        );
+        assert_eq!(partial(5..10, &[(5, 7), (7, 10)]).missing(), vec![]);
+    }
+
+    #[test]
+    fn contiguous_stops_at_the_first_gap() {
+        let (items, height) = partial(0..10, &[(0, 3), (3, 5), (7, 10)]).into_contiguous();
+        assert_eq!(items, vec![0, 1, 2, 3, 4]);
+        assert_eq!(height, 5);
+        assert_eq!(partial(0..10, &[(2, 4)]).into_contiguous(), (vec![], 0));
+    }
+
+    #[test]
+    fn check_chunk_wants_one_item_per_height() {
+        let sorted = check_chunk("get_blocks", &(0..3), vec![(2, 'c'), (0, 'a'), (1, 'b')]);
+        assert_eq!(sorted.unwrap(), vec!['a', 'b', 'c']);
+        for items in [
+            vec![(0, 'a'), (1, 'b')],
+            vec![(0, 'a'), (1, 'b'), (1, 'b')],
+            vec![(1, 'b'), (2, 'c'), (3, 'd')],
+            vec![(0, 'a'), (1, 'b'), (2, 'c'), (3, 'd')],
+        ] {
+            let error = check_chunk("get_blocks", &(0..3), items.clone()).unwrap_err();
+            assert!(
+                matches!(error, ChiaRpcError::UnexpectedResponse { .. }),
+                "{:?}",
+                items
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn chunks_finishing_out_of_order_come_back_in_height_order() {
+        let progress = Arc::new(Mutex::new(vec![]));
+        let reported = progress.clone();
+        let options = RangeOptions::new()
+            .chunk_size(2)
+            .concurrency(5)
+            .on_progress(move |p| reported.lock().unwrap().push(p.clone()));
+        let items = fetch_range(
+            empty_range(0, 9),
+            "get_blocks",
+            32,
+            &options,
+            |start, end| {
+                async move {
+                    // Later chunks answer first.
+                    tokio::time::sleep(Duration::from_millis(20 * (10 - start as u64))).await;
+                    Ok((start..end).map(|height| (height, height)).collect())
+                }
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(items, (0..9).collect::<Vec<_>>());
+        let progress = progress.lock().unwrap();
+        let chunks: Vec<_> = progress.iter().map(|p| p.chunk.clone()).collect();
+        assert_eq!(chunks, vec![8..9, 6..8, 4..6, 2..4, 0..2]);
+        let fetched: Vec<_> = progress.iter().map(|p| p.fetched).collect();
+        assert_eq!(fetched, vec![1, 3, 5, 7, 9]);
+        assert!(progress.iter().all(|p| p.total == 9));
+    }
+
+    #[tokio::test]
+    async fn resume_fetches_only_the_missing_heights() {
+        let node = Arc::new(MockTransport::new());
+        node.respond_once("get_block_records", records(0..2))
+            .fail_once(
+                "get_block_records",
+                ChiaRpcError::Unreachable("node".to_string()),
+            );
+        let client = FullnodeClient::from_transport(node.clone());
+        let options = RangeOptions::new().chunk_size(2).concurrency(1);
+        let partial = client
+            .get_block_records_range(0, 6, &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(partial.error, ChiaRpcError::Unreachable(_)));
+        assert_eq!(partial.missing(), vec![2..6]);
+
+        node.respond_once("get_block_records", records(2..4))
+            .respond_once("get_block_records", records(4..6));
+        let all = client
+            .resume_block_records_range(partial, &options)
+            .await
+            .unwrap();
+        let heights: Vec<_> = all.iter().map(|record| record.height).collect();
+        assert_eq!(heights, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            node.requests_to("get_block_records"),
+            vec![
+                json!({ "start": 0, "end": 2 }),
+                json!({ "start": 2, "end": 4 }),
+                json!({ "start": 2, "end": 4 }),
+                json!({ "start": 4, "end": 6 }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_short_chunk_fails_the_range() {
+        let node = Arc::new(MockTransport::new());
+        node.respond("get_block_records", records(0..1));
+        let client = FullnodeClient::from_transport(node);
+        let options = RangeOptions::new().chunk_size(2);
+        let partial = client
+            .get_block_records_range(0, 2, &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            partial.error,
+            ChiaRpcError::UnexpectedResponse { .. }
+        ));
+        assert_eq!(partial.missing(), vec![0..2]);
+    }
+}
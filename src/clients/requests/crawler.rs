@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{PeerCountsResp, PeerIpsResp};
+
+#[derive(Serialize)]
+pub struct GetPeerCounts {}
+
+impl RpcRequest for GetPeerCounts {
+    const ENDPOINT: &'static str = "get_peer_counts";
+    type Response = PeerCountsResp;
+}
+
+#[derive(Serialize)]
+pub struct GetIpsAfterTimestamp {
+    pub after: u64,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+impl RpcRequest for GetIpsAfterTimestamp {
+    const ENDPOINT: &'static str = "get_ips_after_timestamp";
+    type Response = PeerIpsResp;
+}
@@ -0,0 +1,155 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    DataStoreIdResp, DataStoreIdsResp, DataStoreSyncStatusResp, KeysValuesResp, KvDiffResp,
+    MirrorsResp, RootHistoryResp, RootResp, SuccessResp, TxIdResp, ValueResp,
+};
+use crate::clients::types::data_layer::{Change, HexBytes};
+
+#[derive(Serialize)]
+pub struct CreateDataStore {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+}
+
+impl RpcRequest for CreateDataStore {
+    const ENDPOINT: &'static str = "create_data_store";
+    type Response = DataStoreIdResp;
+}
+
+#[derive(Serialize)]
+pub struct GetOwnedStores {}
+
+impl RpcRequest for GetOwnedStores {
+    const ENDPOINT: &'static str = "get_owned_stores";
+    type Response = DataStoreIdsResp;
+}
+
+#[derive(Serialize)]
+pub struct BatchUpdate<'a> {
+    pub id: &'a Bytes32,
+    pub changelist: &'a [Change],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+}
+
+impl<'a> RpcRequest for BatchUpdate<'a> {
+    const ENDPOINT: &'static str = "batch_update";
+    type Response = TxIdResp;
+}
+
+/// Reads at `root_hash`, or at the current root when `None`.
+#[derive(Serialize)]
+pub struct GetKeysValues<'a> {
+    pub id: &'a Bytes32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_hash: Option<&'a Bytes32>,
+}
+
+impl<'a> RpcRequest for GetKeysValues<'a> {
+    const ENDPOINT: &'static str = "get_keys_values";
+    type Response = KeysValuesResp;
+}
+
+#[derive(Serialize)]
+pub struct GetValue<'a> {
+    pub id: &'a Bytes32,
+    pub key: &'a HexBytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_hash: Option<&'a Bytes32>,
+}
+
+impl<'a> RpcRequest for GetValue<'a> {
+    const ENDPOINT: &'static str = "get_value";
+    type Response = ValueResp;
+}
+
+#[derive(Serialize)]
+pub struct GetRoot<'a> {
+    pub id: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetRoot<'a> {
+    const ENDPOINT: &'static str = "get_root";
+    type Response = RootResp;
+}
+
+#[derive(Serialize)]
+pub struct GetRootHistory<'a> {
+    pub id: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetRootHistory<'a> {
+    const ENDPOINT: &'static str = "get_root_history";
+    type Response = RootHistoryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetKvDiff<'a> {
+    pub id: &'a Bytes32,
+    pub hash_1: &'a Bytes32,
+    pub hash_2: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetKvDiff<'a> {
+    const ENDPOINT: &'static str = "get_kv_diff";
+    type Response = KvDiffResp;
+}
+
+#[derive(Serialize)]
+pub struct Subscribe<'a> {
+    pub id: &'a Bytes32,
+    pub urls: &'a [String],
+}
+
+impl<'a> RpcRequest for Subscribe<'a> {
+    const ENDPOINT: &'static str = "subscribe";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct Unsubscribe<'a> {
+    pub id: &'a Bytes32,
+    pub retain: bool,
+}
+
+impl<'a> RpcRequest for Unsubscribe<'a> {
+    const ENDPOINT: &'static str = "unsubscribe";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct AddMirror<'a> {
+    pub id: &'a Bytes32,
+    pub urls: &'a [String],
+    pub amount: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+}
+
+impl<'a> RpcRequest for AddMirror<'a> {
+    const ENDPOINT: &'static str = "add_mirror";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetMirrors<'a> {
+    pub id: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetMirrors<'a> {
+    const ENDPOINT: &'static str = "get_mirrors";
+    type Response = MirrorsResp;
+}
+
+#[derive(Serialize)]
+pub struct GetSyncStatus<'a> {
+    pub id: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetSyncStatus<'a> {
+    const ENDPOINT: &'static str = "get_sync_status";
+    type Response = DataStoreSyncStatusResp;
+}
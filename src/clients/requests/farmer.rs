@@ -0,0 +1,159 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    FarmerSignagePointAryResp, FarmerSignagePointResp, HarvesterDetailsAryResp,
+    HarvesterSummaryAryResp, LoginLinkResp, PaginatedPlotsResp, PoolStateResp, RewardTargetsResp,
+    SuccessResp,
+};
+use crate::clients::types::harvester::{PlotFilter, PlotInfo};
+
+#[derive(Serialize)]
+pub struct GetSignagePoint<'a> {
+    pub sp_hash: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetSignagePoint<'a> {
+    const ENDPOINT: &'static str = "get_signage_point";
+    type Response = FarmerSignagePointResp;
+}
+
+#[derive(Serialize)]
+pub struct GetSignagePoints {}
+
+impl RpcRequest for GetSignagePoints {
+    const ENDPOINT: &'static str = "get_signage_points";
+    type Response = FarmerSignagePointAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetRewardTargets {
+    pub search_for_private_key: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_ph_to_search: Option<u32>,
+}
+
+impl RpcRequest for GetRewardTargets {
+    const ENDPOINT: &'static str = "get_reward_targets";
+    type Response = RewardTargetsResp;
+}
+
+/// Unset targets are left as they are.
+#[derive(Serialize)]
+pub struct SetRewardTargets<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub farmer_target: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_target: Option<&'a str>,
+}
+
+impl<'a> RpcRequest for SetRewardTargets<'a> {
+    const ENDPOINT: &'static str = "set_reward_targets";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetPoolState {}
+
+impl RpcRequest for GetPoolState {
+    const ENDPOINT: &'static str = "get_pool_state";
+    type Response = PoolStateResp;
+}
+
+#[derive(Serialize)]
+pub struct SetPayoutInstructions<'a> {
+    pub launcher_id: &'a Bytes32,
+    pub payout_instructions: &'a str,
+}
+
+impl<'a> RpcRequest for SetPayoutInstructions<'a> {
+    const ENDPOINT: &'static str = "set_payout_instructions";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetHarvesters {}
+
+impl RpcRequest for GetHarvesters {
+    const ENDPOINT: &'static str = "get_harvesters";
+    type Response = HarvesterDetailsAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetHarvestersSummary {}
+
+impl RpcRequest for GetHarvestersSummary {
+    const ENDPOINT: &'static str = "get_harvesters_summary";
+    type Response = HarvesterSummaryAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetHarvesterPlotsValid<'a> {
+    pub node_id: &'a Bytes32,
+    pub page: u32,
+    pub page_size: u32,
+    pub filter: &'a [PlotFilter],
+    pub sort_key: &'a str,
+    pub reverse: bool,
+}
+
+impl<'a> RpcRequest for GetHarvesterPlotsValid<'a> {
+    const ENDPOINT: &'static str = "get_harvester_plots_valid";
+    type Response = PaginatedPlotsResp<PlotInfo>;
+}
+
+/// Page of the filenames a harvester failed to open, `filter` matches parts of the filename.
+#[derive(Serialize)]
+pub struct GetHarvesterPlotsInvalid<'a> {
+    pub node_id: &'a Bytes32,
+    pub page: u32,
+    pub page_size: u32,
+    pub filter: &'a [String],
+    pub reverse: bool,
+}
+
+impl<'a> RpcRequest for GetHarvesterPlotsInvalid<'a> {
+    const ENDPOINT: &'static str = "get_harvester_plots_invalid";
+    type Response = PaginatedPlotsResp<String>;
+}
+
+/// Page of the filenames of plots whose keys are missing, `filter` matches parts of the filename.
+#[derive(Serialize)]
+pub struct GetHarvesterPlotsKeysMissing<'a> {
+    pub node_id: &'a Bytes32,
+    pub page: u32,
+    pub page_size: u32,
+    pub filter: &'a [String],
+    pub reverse: bool,
+}
+
+impl<'a> RpcRequest for GetHarvesterPlotsKeysMissing<'a> {
+    const ENDPOINT: &'static str = "get_harvester_plots_keys_missing";
+    type Response = PaginatedPlotsResp<String>;
+}
+
+/// Page of the filenames of duplicate plots, `filter` matches parts of the filename.
+#[derive(Serialize)]
+pub struct GetHarvesterPlotsDuplicates<'a> {
+    pub node_id: &'a Bytes32,
+    pub page: u32,
+    pub page_size: u32,
+    pub filter: &'a [String],
+    pub reverse: bool,
+}
+
+impl<'a> RpcRequest for GetHarvesterPlotsDuplicates<'a> {
+    const ENDPOINT: &'static str = "get_harvester_plots_duplicates";
+    type Response = PaginatedPlotsResp<String>;
+}
+
+#[derive(Serialize)]
+pub struct GetPoolLoginLink<'a> {
+    pub launcher_id: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetPoolLoginLink<'a> {
+    const ENDPOINT: &'static str = "get_pool_login_link";
+    type Response = LoginLinkResp;
+}
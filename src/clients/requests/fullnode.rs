@@ -0,0 +1,224 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use druid_garden_chia_types::blockchain::spend_bundle::SpendBundle;
+use serde::Serialize;
+
+pub use crate::clients::requests::service::GetNetworkInfo;
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    AdditionsAndRemovalsResp, BlockRecordAryResp, BlockRecordResp, BlockchainStateResp,
+    CoinRecordAryResp, CoinRecordResp, CoinSpendResp, FullBlockAryResp, FullBlockResp,
+    InitialFreezePeriodResp, MempoolItemResp, MempoolItemsResp, MempoolTXResp, NetSpaceResp,
+    SignagePointOrEOSResp, TXResp, UnfinishedBlockAryResp,
+};
+
+#[derive(Serialize)]
+pub struct GetBlockchainState {}
+
+impl RpcRequest for GetBlockchainState {
+    const ENDPOINT: &'static str = "get_blockchain_state";
+    type Response = BlockchainStateResp;
+}
+
+#[derive(Serialize)]
+pub struct GetBlock<'a> {
+    pub header_hash: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetBlock<'a> {
+    const ENDPOINT: &'static str = "get_block";
+    type Response = FullBlockResp;
+}
+
+/// Blocks from `start` up to but excluding `end`.
+#[derive(Serialize)]
+pub struct GetBlocks {
+    pub start: u32,
+    pub end: u32,
+    pub exclude_header_hash: bool,
+    /// Leaves out blocks of orphaned forks the node still stores at the requested heights.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_reorged: bool,
+}
+
+impl RpcRequest for GetBlocks {
+    const ENDPOINT: &'static str = "get_blocks";
+    type Response = FullBlockAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetBlockRecordByHeight {
+    pub height: u32,
+}
+
+impl RpcRequest for GetBlockRecordByHeight {
+    const ENDPOINT: &'static str = "get_block_record_by_height";
+    type Response = BlockRecordResp;
+}
+
+#[derive(Serialize)]
+pub struct GetBlockRecord<'a> {
+    pub header_hash: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetBlockRecord<'a> {
+    const ENDPOINT: &'static str = "get_block_record";
+    type Response = BlockRecordResp;
+}
+
+#[derive(Serialize)]
+pub struct GetBlockRecords {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl RpcRequest for GetBlockRecords {
+    const ENDPOINT: &'static str = "get_block_records";
+    type Response = BlockRecordAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetUnfinishedBlockHeaders {}
+
+impl RpcRequest for GetUnfinishedBlockHeaders {
+    const ENDPOINT: &'static str = "get_unfinished_block_headers";
+    type Response = UnfinishedBlockAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetNetworkSpace<'a> {
+    pub older_block_header_hash: &'a Bytes32,
+    pub newer_block_header_hash: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetNetworkSpace<'a> {
+    const ENDPOINT: &'static str = "get_network_space";
+    type Response = NetSpaceResp;
+}
+
+#[derive(Serialize)]
+pub struct GetAdditionsAndRemovals<'a> {
+    pub header_hash: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetAdditionsAndRemovals<'a> {
+    const ENDPOINT: &'static str = "get_additions_and_removals";
+    type Response = AdditionsAndRemovalsResp;
+}
+
+#[derive(Serialize)]
+pub struct GetInitialFreezePeriod {}
+
+impl RpcRequest for GetInitialFreezePeriod {
+    const ENDPOINT: &'static str = "get_initial_freeze_period";
+    type Response = InitialFreezePeriodResp;
+}
+
+/// Exactly one of `sp_hash` and `challenge_hash` must be set.
+#[derive(Serialize)]
+pub struct GetRecentSignagePointOrEos<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp_hash: Option<&'a Bytes32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_hash: Option<&'a Bytes32>,
+}
+
+impl<'a> RpcRequest for GetRecentSignagePointOrEos<'a> {
+    const ENDPOINT: &'static str = "get_recent_signage_point_or_eos";
+    type Response = SignagePointOrEOSResp;
+}
+
+#[derive(Serialize)]
+pub struct GetCoinRecordsByPuzzleHash<'a> {
+    pub puzzle_hash: &'a Bytes32,
+    pub include_spent_coins: bool,
+    pub start_height: u32,
+    pub end_height: u32,
+}
+
+impl<'a> RpcRequest for GetCoinRecordsByPuzzleHash<'a> {
+    const ENDPOINT: &'static str = "get_coin_records_by_puzzle_hash";
+    type Response = CoinRecordAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetCoinRecordsByPuzzleHashes<'a> {
+    pub puzzle_hashes: Vec<&'a Bytes32>,
+    pub include_spent_coins: bool,
+    pub start_height: u32,
+    pub end_height: u32,
+}
+
+impl<'a> RpcRequest for GetCoinRecordsByPuzzleHashes<'a> {
+    const ENDPOINT: &'static str = "get_coin_records_by_puzzle_hashes";
+    type Response = CoinRecordAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetCoinRecordByName<'a> {
+    pub name: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for GetCoinRecordByName<'a> {
+    const ENDPOINT: &'static str = "get_coin_record_by_name";
+    type Response = CoinRecordResp;
+}
+
+#[derive(Serialize)]
+pub struct GetCoinRecordsByParentIds<'a> {
+    pub parent_ids: Vec<&'a Bytes32>,
+    pub include_spent_coins: bool,
+    pub start_height: u32,
+    pub end_height: u32,
+}
+
+impl<'a> RpcRequest for GetCoinRecordsByParentIds<'a> {
+    const ENDPOINT: &'static str = "get_coin_records_by_parent_ids";
+    type Response = CoinRecordAryResp;
+}
+
+#[derive(Serialize)]
+pub struct PushTx<'a> {
+    pub spend_bundle: &'a SpendBundle,
+}
+
+impl<'a> RpcRequest for PushTx<'a> {
+    const ENDPOINT: &'static str = "push_tx";
+    type Response = TXResp;
+}
+
+#[derive(Serialize)]
+pub struct GetPuzzleAndSolution<'a> {
+    pub coin_id: &'a Bytes32,
+    pub height: u32,
+}
+
+impl<'a> RpcRequest for GetPuzzleAndSolution<'a> {
+    const ENDPOINT: &'static str = "get_puzzle_and_solution";
+    type Response = CoinSpendResp;
+}
+
+#[derive(Serialize)]
+pub struct GetAllMempoolTxIds {}
+
+impl RpcRequest for GetAllMempoolTxIds {
+    const ENDPOINT: &'static str = "get_all_mempool_tx_ids";
+    type Response = MempoolTXResp;
+}
+
+#[derive(Serialize)]
+pub struct GetAllMempoolItems {}
+
+impl RpcRequest for GetAllMempoolItems {
+    const ENDPOINT: &'static str = "get_all_mempool_items";
+    type Response = MempoolItemsResp;
+}
+
+#[derive(Serialize)]
+pub struct GetMempoolItemByTxId<'a> {
+    pub tx_id: &'a str,
+}
+
+impl<'a> RpcRequest for GetMempoolItemByTxId<'a> {
+    const ENDPOINT: &'static str = "get_mempool_item_by_tx_id";
+    type Response = MempoolItemResp;
+}
@@ -0,0 +1,82 @@
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    HarvesterConfigResp, HarvesterPlotsResp, PlotDirectoriesResp, SuccessResp,
+};
+use crate::clients::types::harvester::HarvesterConfig;
+
+#[derive(Serialize)]
+pub struct GetPlots {}
+
+impl RpcRequest for GetPlots {
+    const ENDPOINT: &'static str = "get_plots";
+    type Response = HarvesterPlotsResp;
+}
+
+#[derive(Serialize)]
+pub struct RefreshPlots {}
+
+impl RpcRequest for RefreshPlots {
+    const ENDPOINT: &'static str = "refresh_plots";
+    type Response = SuccessResp;
+}
+
+/// Deletes the plot file from disk, not only from the harvester.
+#[derive(Serialize)]
+pub struct DeletePlot<'a> {
+    pub filename: &'a str,
+}
+
+impl<'a> RpcRequest for DeletePlot<'a> {
+    const ENDPOINT: &'static str = "delete_plot";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct AddPlotDirectory<'a> {
+    pub dirname: &'a str,
+}
+
+impl<'a> RpcRequest for AddPlotDirectory<'a> {
+    const ENDPOINT: &'static str = "add_plot_directory";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetPlotDirectories {}
+
+impl RpcRequest for GetPlotDirectories {
+    const ENDPOINT: &'static str = "get_plot_directories";
+    type Response = PlotDirectoriesResp;
+}
+
+#[derive(Serialize)]
+pub struct RemovePlotDirectory<'a> {
+    pub dirname: &'a str,
+}
+
+impl<'a> RpcRequest for RemovePlotDirectory<'a> {
+    const ENDPOINT: &'static str = "remove_plot_directory";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetHarvesterConfig {}
+
+impl RpcRequest for GetHarvesterConfig {
+    const ENDPOINT: &'static str = "get_harvester_config";
+    type Response = HarvesterConfigResp;
+}
+
+/// Sends the fields of the config that are set as the body.
+#[derive(Serialize)]
+#[serde(transparent)]
+pub struct UpdateHarvesterConfig<'a> {
+    pub config: &'a HarvesterConfig,
+}
+
+impl<'a> RpcRequest for UpdateHarvesterConfig<'a> {
+    const ENDPOINT: &'static str = "update_harvester_config";
+    type Response = SuccessResp;
+}
@@ -0,0 +1,21 @@
+//! Typed request bodies, each paired with its route and response type through [`RpcRequest`].
+//!
+//! Send them with the client's `request` method, ie
+//! `client.request(&GetBlock { header_hash: &hash }).await?.block`.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub mod crawler;
+pub mod data_layer;
+pub mod farmer;
+pub mod fullnode;
+pub mod harvester;
+pub mod service;
+pub mod simulator;
+pub mod wallet;
+
+pub trait RpcRequest: Serialize + Sync {
+    /// Route of the endpoint, without the leading slash.
+    const ENDPOINT: &'static str;
+    type Response: DeserializeOwned;
+}
@@ -0,0 +1,81 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    ConnectionsResp, NetworkInfoResp, RoutesResp, SuccessResp, VersionResp,
+};
+use crate::clients::types::connection::NodeType;
+
+/// Lists every peer when `node_type` is `None`.
+#[derive(Serialize)]
+pub struct GetConnections {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<NodeType>,
+}
+
+impl RpcRequest for GetConnections {
+    const ENDPOINT: &'static str = "get_connections";
+    type Response = ConnectionsResp;
+}
+
+#[derive(Serialize)]
+pub struct OpenConnection<'a> {
+    pub host: &'a str,
+    pub port: u16,
+}
+
+impl<'a> RpcRequest for OpenConnection<'a> {
+    const ENDPOINT: &'static str = "open_connection";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct CloseConnection<'a> {
+    pub node_id: &'a Bytes32,
+}
+
+impl<'a> RpcRequest for CloseConnection<'a> {
+    const ENDPOINT: &'static str = "close_connection";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct StopNode {}
+
+impl RpcRequest for StopNode {
+    const ENDPOINT: &'static str = "stop_node";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetRoutes {}
+
+impl RpcRequest for GetRoutes {
+    const ENDPOINT: &'static str = "get_routes";
+    type Response = RoutesResp;
+}
+
+#[derive(Serialize)]
+pub struct Healthz {}
+
+impl RpcRequest for Healthz {
+    const ENDPOINT: &'static str = "healthz";
+    type Response = SuccessResp;
+}
+
+#[derive(Serialize)]
+pub struct GetVersion {}
+
+impl RpcRequest for GetVersion {
+    const ENDPOINT: &'static str = "get_version";
+    type Response = VersionResp;
+}
+
+#[derive(Serialize)]
+pub struct GetNetworkInfo {}
+
+impl RpcRequest for GetNetworkInfo {
+    const ENDPOINT: &'static str = "get_network_info";
+    type Response = NetworkInfoResp;
+}
@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    AutoFarmingResp, CoinRecordAryResp, FarmingPuzzleHashResp, NewPeakHeightResp, PuzzleHashesResp,
+};
+
+/// With `guarantee_tx_block` the last block farmed is a transaction block.
+#[derive(Serialize)]
+pub struct FarmBlock<'a> {
+    pub address: &'a str,
+    pub blocks: u32,
+    pub guarantee_tx_block: bool,
+}
+
+impl<'a> RpcRequest for FarmBlock<'a> {
+    const ENDPOINT: &'static str = "farm_block";
+    type Response = NewPeakHeightResp;
+}
+
+#[derive(Serialize)]
+pub struct SetAutoFarming {
+    pub auto_farm: bool,
+}
+
+impl RpcRequest for SetAutoFarming {
+    const ENDPOINT: &'static str = "set_auto_farming";
+    type Response = AutoFarmingResp;
+}
+
+#[derive(Serialize)]
+pub struct GetAutoFarming {}
+
+impl RpcRequest for GetAutoFarming {
+    const ENDPOINT: &'static str = "get_auto_farming";
+    type Response = AutoFarmingResp;
+}
+
+#[derive(Serialize)]
+pub struct GetFarmingPh {}
+
+impl RpcRequest for GetFarmingPh {
+    const ENDPOINT: &'static str = "get_farming_ph";
+    type Response = FarmingPuzzleHashResp;
+}
+
+#[derive(Serialize)]
+pub struct RevertBlocks {
+    pub num_of_blocks: u32,
+    pub delete_all_blocks: bool,
+}
+
+impl RpcRequest for RevertBlocks {
+    const ENDPOINT: &'static str = "revert_blocks";
+    type Response = NewPeakHeightResp;
+}
+
+#[derive(Serialize)]
+pub struct ReorgBlocks {
+    pub num_of_blocks_to_rev: u32,
+    pub num_of_new_blocks: u32,
+    pub revert_all_blocks: bool,
+}
+
+impl RpcRequest for ReorgBlocks {
+    const ENDPOINT: &'static str = "reorg_blocks";
+    type Response = NewPeakHeightResp;
+}
+
+#[derive(Serialize)]
+pub struct GetAllCoins {
+    pub include_spent_coins: bool,
+}
+
+impl RpcRequest for GetAllCoins {
+    const ENDPOINT: &'static str = "get_all_coins";
+    type Response = CoinRecordAryResp;
+}
+
+#[derive(Serialize)]
+pub struct GetAllPuzzleHashes {}
+
+impl RpcRequest for GetAllPuzzleHashes {
+    const ENDPOINT: &'static str = "get_all_puzzle_hashes";
+    type Response = PuzzleHashesResp;
+}
@@ -0,0 +1,104 @@
+use druid_garden_chia_types::blockchain::coin::Coin;
+use druid_garden_chia_types::blockchain::pending_payment::PendingPayment;
+use serde::Serialize;
+
+use crate::clients::requests::RpcRequest;
+use crate::clients::responses::{
+    LoginResp, SignedTransactionRecordResp, TransactionRecordResp, WalletBalanceResp,
+    WalletInfoResp, WalletSyncResp,
+};
+
+#[derive(Serialize)]
+pub struct LogIn {
+    pub wallet_fingerprint: u32,
+}
+
+impl RpcRequest for LogIn {
+    const ENDPOINT: &'static str = "log_in";
+    type Response = LoginResp;
+}
+
+#[derive(Serialize)]
+pub struct LogInAndSkip {
+    pub wallet_fingerprint: u32,
+}
+
+impl RpcRequest for LogInAndSkip {
+    const ENDPOINT: &'static str = "log_in_and_skip";
+    type Response = LoginResp;
+}
+
+#[derive(Serialize)]
+pub struct GetWallets {}
+
+impl RpcRequest for GetWallets {
+    const ENDPOINT: &'static str = "get_wallets";
+    type Response = WalletInfoResp;
+}
+
+#[derive(Serialize)]
+pub struct GetWalletBalance {
+    pub wallet_id: u32,
+}
+
+impl RpcRequest for GetWalletBalance {
+    const ENDPOINT: &'static str = "get_wallet_balance";
+    type Response = WalletBalanceResp;
+}
+
+#[derive(Serialize)]
+pub struct GetSyncStatus {}
+
+impl RpcRequest for GetSyncStatus {
+    const ENDPOINT: &'static str = "get_sync_status";
+    type Response = WalletSyncResp;
+}
+
+#[derive(Serialize)]
+pub struct SendTransaction<'a> {
+    pub wallet_id: u32,
+    pub amount: u64,
+    pub address: &'a str,
+    pub fee: u64,
+}
+
+impl<'a> RpcRequest for SendTransaction<'a> {
+    const ENDPOINT: &'static str = "send_transaction";
+    type Response = TransactionRecordResp;
+}
+
+#[derive(Serialize)]
+pub struct SendTransactionMulti<'a> {
+    pub wallet_id: u32,
+    pub additions: &'a [PendingPayment],
+    pub fee: u64,
+}
+
+impl<'a> RpcRequest for SendTransactionMulti<'a> {
+    const ENDPOINT: &'static str = "send_transaction_multi";
+    type Response = TransactionRecordResp;
+}
+
+#[derive(Serialize)]
+pub struct GetTransaction<'a> {
+    pub wallet_id: u32,
+    pub transaction_id: &'a str,
+}
+
+impl<'a> RpcRequest for GetTransaction<'a> {
+    const ENDPOINT: &'static str = "get_transaction";
+    type Response = TransactionRecordResp;
+}
+
+#[derive(Serialize)]
+pub struct CreateSignedTransaction<'a> {
+    pub wallet_id: u32,
+    pub additions: &'a [Coin],
+    pub coins: &'a [Coin],
+    pub fee: u64,
+}
+
+impl<'a> RpcRequest for CreateSignedTransaction<'a> {
+    const ENDPOINT: &'static str = "create_signed_transaction";
+    type Response = SignedTransactionRecordResp;
+}
@@ -1,10 +1,14 @@
+//! Full bodies of the RPC responses, as returned by the clients' `request` and `*_full` methods.
+//!
+//! Fields the node sends that are not modelled here are kept in each response's `extra` map.
 use druid_garden_chia_types::blockchain::block_record::BlockRecord;
 use druid_garden_chia_types::blockchain::blockchain_state::BlockchainState;
 use druid_garden_chia_types::blockchain::coin_record::CoinRecord;
 use druid_garden_chia_types::blockchain::coin_spend::CoinSpend;
 use druid_garden_chia_types::blockchain::full_block::FullBlock;
-use druid_garden_chia_types::blockchain::mem_pool_item::MemPoolItem;
+use druid_garden_chia_types::blockchain::mempool_item::MempoolItem;
 use druid_garden_chia_types::blockchain::signage_point::SignagePoint;
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
 use druid_garden_chia_types::blockchain::subslot_bundle::SubSlotBundle;
 use druid_garden_chia_types::blockchain::transaction_record::TransactionRecord;
 use druid_garden_chia_types::blockchain::tx_status::TXStatus;
@@ -12,7 +16,17 @@ use druid_garden_chia_types::blockchain::unfinished_block::UnfinishedBlock;
 use druid_garden_chia_types::blockchain::wallet_balance::WalletBalance;
 use druid_garden_chia_types::blockchain::wallet_info::WalletInfo;
 
+use crate::clients::types::connection::PeerConnection;
+use crate::clients::types::crawler::{PeerCounts, PeerIps};
+use crate::clients::types::data_layer::{
+    DataStoreSyncStatus, HexBytes, KeyValue, KvDiff, Mirror, Root, RootHistoryEntry,
+};
+use crate::clients::types::farmer::{FarmerSignagePoint, PoolState, RewardTargets};
+use crate::clients::types::harvester::{
+    HarvesterConfig, HarvesterDetails, HarvesterPlots, HarvesterSummary, PaginatedPlots,
+};
 use serde::Deserialize;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
 #[derive(Deserialize)]
@@ -20,83 +34,242 @@ pub struct AdditionsAndRemovalsResp {
     pub additions: Vec<CoinRecord>,
     pub removals: Vec<CoinRecord>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct AutoFarmingResp {
+    pub auto_farm_enabled: bool,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct BlockchainStateResp {
     pub blockchain_state: BlockchainState,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct BlockRecordResp {
     pub block_record: BlockRecord,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct BlockRecordAryResp {
     pub block_records: Vec<BlockRecord>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct CoinRecordResp {
     pub coin_record: Option<CoinRecord>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct CoinRecordAryResp {
     pub coin_records: Vec<CoinRecord>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct CoinSpendResp {
     pub coin_solution: CoinSpend,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct ConnectionsResp {
+    pub connections: Vec<PeerConnection>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct DataStoreIdResp {
+    pub id: Bytes32,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct DataStoreIdsResp {
+    pub store_ids: Vec<Bytes32>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct DataStoreSyncStatusResp {
+    pub sync_status: DataStoreSyncStatus,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct FarmingPuzzleHashResp {
+    pub puzzle_hash: Bytes32,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct FarmerSignagePointAryResp {
+    pub signage_points: Vec<FarmerSignagePoint>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct FarmerSignagePointResp {
+    #[serde(flatten)]
+    pub signage_point: FarmerSignagePoint,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct FullBlockResp {
     pub block: FullBlock,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct FullBlockAryResp {
     pub blocks: Vec<FullBlock>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct HarvesterConfigResp {
+    #[serde(flatten)]
+    pub config: HarvesterConfig,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct HarvesterDetailsAryResp {
+    pub harvesters: Vec<HarvesterDetails>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct HarvesterPlotsResp {
+    #[serde(flatten)]
+    pub plots: HarvesterPlots,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct HarvesterSummaryAryResp {
+    pub harvesters: Vec<HarvesterSummary>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct InitialFreezePeriodResp {
     pub initial_freeze_end_timestamp: u64,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct KeysValuesResp {
+    pub keys_values: Vec<KeyValue>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct KvDiffResp {
+    pub diff: Vec<KvDiff>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct LoginLinkResp {
+    pub login_link: String,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct LoginResp {
     pub fingerprint: u32,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct MempoolItemResp {
-    pub mempool_item: MemPoolItem,
+    pub mempool_item: MempoolItem,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct MempoolItemsResp {
-    pub mempool_items: HashMap<String, MemPoolItem>,
+    pub mempool_items: HashMap<String, MempoolItem>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 #[derive(Deserialize)]
 pub struct MempoolTXResp {
     pub tx_ids: Vec<String>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct MirrorsResp {
+    pub mirrors: Vec<Mirror>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
@@ -104,12 +277,108 @@ pub struct NetworkInfoResp {
     pub network_name: String,
     pub network_prefix: String,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct NetSpaceResp {
     pub space: u64,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct NewPeakHeightResp {
+    pub new_peak_height: u32,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PaginatedPlotsResp<T> {
+    #[serde(flatten)]
+    pub page: PaginatedPlots<T>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PeerCountsResp {
+    pub peer_counts: PeerCounts,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PeerIpsResp {
+    #[serde(flatten)]
+    pub peers: PeerIps,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PlotDirectoriesResp {
+    pub directories: Vec<String>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PoolStateResp {
+    pub pool_state: Vec<PoolState>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct PuzzleHashesResp {
+    pub puzzle_hashes: HashMap<String, (u64, u64)>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct RewardTargetsResp {
+    #[serde(flatten)]
+    pub targets: RewardTargets,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct RootHistoryResp {
+    pub root_history: Vec<RootHistoryEntry>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct RootResp {
+    #[serde(flatten)]
+    pub root: Root,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct RoutesResp {
+    pub routes: Vec<String>,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
@@ -119,42 +388,87 @@ pub struct SignagePointOrEOSResp {
     pub time_received: f64,
     pub reverted: bool,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct SignedTransactionRecordResp {
     pub signed_tx: TransactionRecord,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct SuccessResp {
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct TXResp {
     pub status: TXStatus,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct TxIdResp {
+    pub tx_id: Bytes32,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct TransactionRecordResp {
     pub transaction: TransactionRecord,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct UnfinishedBlockAryResp {
     pub headers: Vec<UnfinishedBlock>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct ValueResp {
+    pub value: HexBytes,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+pub struct VersionResp {
+    pub version: String,
+    pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct WalletBalanceResp {
     pub wallets: Vec<WalletBalance>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
 pub struct WalletInfoResp {
     pub wallets: Vec<WalletInfo>,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize)]
@@ -163,4 +477,48 @@ pub struct WalletSyncResp {
     pub synced: bool,
     pub syncing: bool,
     pub success: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flattened_bodies_keep_only_unknown_fields_in_extra() {
+        let resp: RewardTargetsResp = serde_json::from_value(json!({
+            "farmer_target": "xch1farmer",
+            "pool_target": "xch1pool",
+            "have_farmer_sk": true,
+            "have_pool_sk": false,
+            "success": true,
+            "new_field": 1,
+        }))
+        .unwrap();
+        assert_eq!(resp.targets.farmer_target, "xch1farmer");
+        assert_eq!(resp.targets.have_pool_sk, Some(false));
+        assert!(resp.success);
+        assert_eq!(
+            resp.extra,
+            json!({ "new_field": 1 }).as_object().unwrap().clone()
+        );
+    }
+
+    #[test]
+    fn paginated_plots_envelope() {
+        let resp: PaginatedPlotsResp<String> = serde_json::from_value(json!({
+            "node_id": format!("0x{}", "00".repeat(32)),
+            "page": 1,
+            "page_count": 3,
+            "total_count": 5,
+            "plots": ["a.plot", "b.plot"],
+            "success": true,
+        }))
+        .unwrap();
+        assert_eq!(resp.page.plots, ["a.plot", "b.plot"]);
+        assert_eq!(resp.page.page_count, 3);
+        assert!(resp.extra.is_empty());
+    }
 }
@@ -0,0 +1,275 @@
+use crate::clients::error::ChiaRpcError;
+use crate::clients::transport::RpcTransport;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Endpoints that spend coins or change wallet state, a retry after a lost response could send
+/// the transaction twice. They are never retried, whatever the policy says.
+pub const NEVER_RETRY: [&str; 6] = [
+    "push_tx",
+    "send_transaction",
+    "send_transaction_multi",
+    "create_signed_transaction",
+    "cat_spend",
+    "stop_node",
+];
+
+/// Decides whether an error is worth another attempt.
+pub type RetryClassifier = Arc<dyn Fn(&ChiaRpcError) -> bool + Send + Sync>;
+
+/// How [`RetryTransport`] retries failed calls.
+///
+/// By default only read endpoints, those named `get_*` plus `healthz`, are retried, up to 4
+/// attempts with exponential backoff from 200ms capped at 5s, on the errors of
+/// [`ChiaRpcError::is_retryable`].
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    extra_endpoints: HashSet<String>,
+    classifier: RetryClassifier,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: true,
+            extra_endpoints: HashSet::new(),
+            classifier: Arc::new(ChiaRpcError::is_retryable),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Total number of attempts including the first, 1 disables retries.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+    /// Randomizes each delay between half and all of the backoff, on by default, so clients
+    /// restarted together do not retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+    /// Also retries `endpoint`, for calls that are safe to repeat but not named `get_*`.
+    /// Endpoints in [`NEVER_RETRY`] are still never retried.
+    pub fn retry_endpoint(mut self, endpoint: &str) -> Self {
+        self.extra_endpoints.insert(endpoint.to_string());
+        self
+    }
+    /// Replaces [`ChiaRpcError::is_retryable`] as the test of which errors to retry.
+    pub fn retry_if<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&ChiaRpcError) -> bool + Send + Sync + 'static,
+    {
+        self.classifier = Arc::new(classifier);
+        self
+    }
+    pub fn is_idempotent(&self, endpoint: &str) -> bool {
+        !NEVER_RETRY.contains(&endpoint)
+            && (endpoint.starts_with("get_")
+                || endpoint == "healthz"
+                || self.extra_endpoints.contains(endpoint))
+    }
+    /// Delay before attempt `attempt + 1`, counting the first attempt as 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(63) as i32;
+        let seconds = self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let backoff = Duration::from_secs_f64(seconds.min(self.max_backoff.as_secs_f64()));
+        if self.jitter {
+            backoff.mul_f64(0.5 + random_fraction() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Retries the calls of `inner` according to a [`RetryPolicy`], see
+/// [`crate::clients::builder::RpcClientBuilder::retry`].
+pub struct RetryTransport {
+    inner: Arc<dyn RpcTransport>,
+    policy: RetryPolicy,
+}
+
+impl RetryTransport {
+    pub fn new(inner: Arc<dyn RpcTransport>, policy: RetryPolicy) -> Self {
+        RetryTransport { inner, policy }
+    }
+}
+
+#[async_trait]
+impl RpcTransport for RetryTransport {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        if !self.policy.is_idempotent(endpoint) {
+            return self.inner.send(endpoint, body).await;
+        }
+        let mut attempt = 1;
+        loop {
+            match self.inner.send(endpoint, body).await {
+                Err(e) if attempt < self.policy.max_attempts && (self.policy.classifier)(&e) => {
+                    tokio::time::sleep(self.policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// A fraction in `[0, 1)` from the randomly seeded std hasher, which is plenty for jitter.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::transport::MockTransport;
+    use serde_json::json;
+
+    fn unreachable() -> ChiaRpcError {
+        ChiaRpcError::Unreachable("node".to_string())
+    }
+
+    fn retrying(node: &Arc<MockTransport>, max_attempts: u32) -> RetryTransport {
+        let policy = RetryPolicy::new()
+            .max_attempts(max_attempts)
+            .initial_backoff(Duration::from_millis(1))
+            .jitter(false);
+        RetryTransport::new(node.clone(), policy)
+    }
+
+    #[test]
+    fn backoff_grows_up_to_the_cap() {
+        let policy = RetryPolicy::new().jitter(false);
+        let delays: Vec<u64> = (1..=7)
+            .map(|attempt| policy.backoff(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![200, 400, 800, 1600, 3200, 5000, 5000]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_the_backoff() {
+        let policy = RetryPolicy::new().initial_backoff(Duration::from_secs(1));
+        for _ in 0..100 {
+            let delay = policy.backoff(1);
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn multiplier_below_one_keeps_the_backoff() {
+        let policy = RetryPolicy::new().jitter(false).multiplier(0.5);
+        assert_eq!(policy.backoff(3), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn only_reads_are_idempotent() {
+        let policy = RetryPolicy::new()
+            .retry_endpoint("log_in")
+            .retry_endpoint("push_tx");
+        assert!(policy.is_idempotent("get_blockchain_state"));
+        assert!(policy.is_idempotent("healthz"));
+        assert!(policy.is_idempotent("log_in"));
+        assert!(!policy.is_idempotent("push_tx"));
+        assert!(!policy.is_idempotent("send_transaction"));
+        assert!(!policy.is_idempotent("create_new_wallet"));
+    }
+
+    #[tokio::test]
+    async fn reads_are_retried_until_they_succeed() {
+        let node = Arc::new(MockTransport::new());
+        node.fail_once("get_blockchain_state", unreachable())
+            .fail_once(
+                "get_blockchain_state",
+                ChiaRpcError::Timeout("get_blockchain_state".to_string()),
+            )
+            .respond("get_blockchain_state", json!({ "success": true }));
+        let retry = retrying(&node, 4);
+        let resp = retry
+            .send("get_blockchain_state", &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(resp, json!({ "success": true }));
+        assert_eq!(node.requests_to("get_blockchain_state").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn attempts_stop_at_max_attempts() {
+        let node = Arc::new(MockTransport::new());
+        for _ in 0..5 {
+            node.fail_once("get_coin_record_by_name", unreachable());
+        }
+        let retry = retrying(&node, 3);
+        let error = retry
+            .send("get_coin_record_by_name", &json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ChiaRpcError::Unreachable(_)));
+        assert_eq!(node.requests_to("get_coin_record_by_name").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn spends_are_sent_once() {
+        let node = Arc::new(MockTransport::new());
+        let retry = retrying(&node, 4);
+        for endpoint in ["push_tx", "send_transaction"] {
+            node.fail_once(endpoint, unreachable())
+                .respond(endpoint, json!({ "success": true }));
+            let error = retry.send(endpoint, &json!({})).await.unwrap_err();
+            assert!(
+                matches!(error, ChiaRpcError::Unreachable(_)),
+                "{}",
+                endpoint
+            );
+            assert_eq!(node.requests_to(endpoint).len(), 1, "{}", endpoint);
+        }
+    }
+
+    #[tokio::test]
+    async fn node_errors_are_returned_immediately() {
+        let node = Arc::new(MockTransport::new());
+        node.fail_once(
+            "get_block",
+            ChiaRpcError::Rpc {
+                endpoint: "get_block".to_string(),
+                message: "Block not found".to_string(),
+            },
+        )
+        .respond("get_block", json!({ "success": true }));
+        let retry = retrying(&node, 4);
+        let error = retry.send("get_block", &json!({})).await.unwrap_err();
+        assert!(matches!(error, ChiaRpcError::Rpc { .. }));
+        assert_eq!(node.requests_to("get_block").len(), 1);
+    }
+}
@@ -0,0 +1,218 @@
+use async_trait::async_trait;
+use druid_garden_chia_types::blockchain::network_info::NetworkInfo;
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+
+use crate::clients::builder::ServiceClient;
+use crate::clients::common::request;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::requests::service::{
+    CloseConnection, GetConnections, GetNetworkInfo, GetRoutes, GetVersion, Healthz,
+    OpenConnection, StopNode,
+};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+use crate::clients::types::connection::{NodeType, PeerConnection};
+
+/// Endpoints served by every Chia service, implemented for all the RPC clients.
+///
+/// The trait is object safe so clients of different services can be kept together, ie as
+/// `Vec<Box<dyn RpcService>>`.
+#[async_trait]
+pub trait RpcService: Send + Sync {
+    fn service(&self) -> ChiaService;
+    fn rpc_transport(&self) -> &dyn RpcTransport;
+    /// The peers of the service, only those of `node_type` when set.
+    async fn get_connections(
+        &self,
+        node_type: Option<NodeType>,
+    ) -> Result<Vec<PeerConnection>, ChiaRpcError> {
+        Ok(request(self.rpc_transport(), &GetConnections { node_type })
+            .await?
+            .connections)
+    }
+    async fn open_connection(&self, host: &str, port: u16) -> Result<(), ChiaRpcError> {
+        request(self.rpc_transport(), &OpenConnection { host, port }).await?;
+        Ok(())
+    }
+    async fn close_connection(&self, node_id: &Bytes32) -> Result<(), ChiaRpcError> {
+        request(self.rpc_transport(), &CloseConnection { node_id }).await?;
+        Ok(())
+    }
+    /// Shuts the service down, it is not restarted by the daemon.
+    async fn stop_node(&self) -> Result<(), ChiaRpcError> {
+        request(self.rpc_transport(), &StopNode {}).await?;
+        Ok(())
+    }
+    async fn get_routes(&self) -> Result<Vec<String>, ChiaRpcError> {
+        Ok(request(self.rpc_transport(), &GetRoutes {}).await?.routes)
+    }
+    /// Succeeds when the service is up and answering RPCs.
+    async fn healthz(&self) -> Result<(), ChiaRpcError> {
+        request(self.rpc_transport(), &Healthz {}).await?;
+        Ok(())
+    }
+    async fn get_version(&self) -> Result<String, ChiaRpcError> {
+        Ok(request(self.rpc_transport(), &GetVersion {}).await?.version)
+    }
+    async fn get_network_info(&self) -> Result<NetworkInfo, ChiaRpcError> {
+        let resp = request(self.rpc_transport(), &GetNetworkInfo {}).await?;
+        Ok(NetworkInfo {
+            network_name: resp.network_name,
+            network_prefix: resp.network_prefix,
+        })
+    }
+}
+
+impl<C: ServiceClient + Send + Sync> RpcService for C {
+    fn service(&self) -> ChiaService {
+        C::SERVICE
+    }
+    fn rpc_transport(&self) -> &dyn RpcTransport {
+        self.transport().as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::fullnode::FullnodeClient;
+    use crate::clients::harvester::HarvesterClient;
+    use crate::clients::transport::MockTransport;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn client<C: ServiceClient>(transport: &Arc<MockTransport>) -> C {
+        C::from_transport(transport.clone())
+    }
+
+    fn node_id() -> String {
+        format!("0x{}", "02".repeat(32))
+    }
+
+    #[tokio::test]
+    async fn connections_filter_by_node_type() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond(
+            "get_connections",
+            json!({
+                "success": true,
+                "connections": [{
+                    "node_id": node_id(),
+                    "peer_host": "10.0.0.2",
+                    "peer_port": 51_234,
+                    "peer_server_port": 8448,
+                    "type": 2,
+                    "local_port": 8447,
+                    "bytes_read": 1_024,
+                    "bytes_written": 2_048,
+                    "creation_time": 1_700_000_000.5,
+                    "last_message_time": 1_700_000_060.5,
+                    "peak_hash": null,
+                    "peak_height": null,
+                    "peak_weight": null,
+                }],
+            }),
+        );
+        let harvester: HarvesterClient = client(&transport);
+        assert_eq!(harvester.service(), ChiaService::Harvester);
+        let peers = harvester
+            .get_connections(Some(NodeType::Harvester))
+            .await
+            .unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].node_type, NodeType::Harvester);
+        assert_eq!(peers[0].peer_server_port, 8448);
+        assert_eq!(
+            transport.requests_to("get_connections"),
+            [json!({ "node_type": 2 })]
+        );
+        harvester.get_connections(None).await.unwrap();
+        assert_eq!(transport.requests_to("get_connections")[1], json!({}));
+    }
+
+    #[tokio::test]
+    async fn open_and_close_connection() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond("open_connection", json!({ "success": true }))
+            .respond("close_connection", json!({ "success": true }));
+        let node: FullnodeClient = client(&transport);
+        node.open_connection("10.0.0.2", 8444).await.unwrap();
+        let id: Bytes32 = serde_json::from_value(json!(node_id())).unwrap();
+        node.close_connection(&id).await.unwrap();
+        assert_eq!(
+            transport.requests_to("open_connection"),
+            [json!({ "host": "10.0.0.2", "port": 8444 })]
+        );
+        assert_eq!(
+            transport.requests_to("close_connection"),
+            [json!({ "node_id": id })]
+        );
+    }
+
+    #[tokio::test]
+    async fn healthz_and_routes() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond("healthz", json!({ "success": true }))
+            .respond(
+                "get_routes",
+                json!({ "success": true, "routes": ["/get_routes", "/healthz"] }),
+            );
+        let node: FullnodeClient = client(&transport);
+        node.healthz().await.unwrap();
+        assert_eq!(
+            node.get_routes().await.unwrap(),
+            ["/get_routes", "/healthz"]
+        );
+        assert_eq!(transport.requests_to("healthz"), [json!({})]);
+    }
+
+    #[tokio::test]
+    async fn healthz_fails_on_an_error_answer() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("healthz", json!({ "success": false, "error": "starting" }));
+        let node: FullnodeClient = client(&transport);
+        let error = node.healthz().await.unwrap_err();
+        assert!(matches!(error, ChiaRpcError::Rpc { endpoint, .. } if endpoint == "healthz"));
+    }
+
+    #[tokio::test]
+    async fn version_and_network_info() {
+        let transport = Arc::new(MockTransport::new());
+        transport
+            .respond(
+                "get_version",
+                json!({ "success": true, "version": "2.4.4" }),
+            )
+            .respond(
+                "get_network_info",
+                json!({ "success": true, "network_name": "mainnet", "network_prefix": "xch" }),
+            );
+        let node: FullnodeClient = client(&transport);
+        assert_eq!(node.get_version().await.unwrap(), "2.4.4");
+        let network = node.get_network_info().await.unwrap();
+        assert_eq!(network.network_name, "mainnet");
+        assert_eq!(network.network_prefix, "xch");
+        assert_eq!(transport.requests_to("get_version"), [json!({})]);
+        assert_eq!(transport.requests_to("get_network_info"), [json!({})]);
+    }
+
+    #[tokio::test]
+    async fn stop_node() {
+        let transport = Arc::new(MockTransport::new());
+        transport.respond("stop_node", json!({ "success": true }));
+        let services: Vec<Box<dyn RpcService>> = vec![
+            Box::new(client::<FullnodeClient>(&transport)),
+            Box::new(client::<HarvesterClient>(&transport)),
+        ];
+        for service in &services {
+            service.stop_node().await.unwrap();
+        }
+        assert_eq!(transport.requests_to("stop_node").len(), 2);
+        assert!(transport
+            .requests()
+            .iter()
+            .all(|request| request.endpoint == "stop_node"));
+    }
+}
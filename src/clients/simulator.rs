@@ -0,0 +1,151 @@
+use druid_garden_chia_types::blockchain::coin_record::CoinRecord;
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::clients::builder::{client_methods, ServiceClient};
+use crate::clients::common::*;
+use crate::clients::error::ChiaRpcError;
+use crate::clients::fullnode::FullnodeClient;
+use crate::clients::requests::simulator::{
+    FarmBlock, GetAllCoins, GetAllPuzzleHashes, GetAutoFarming, GetFarmingPh, ReorgBlocks,
+    RevertBlocks, SetAutoFarming,
+};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+
+/// Client for the full node of the Chia simulator.
+///
+/// Derefs to [`FullnodeClient`] for the regular full node calls and adds the simulator's chain
+/// control calls.
+///
+/// `from_chia_root` needs `CHIA_ROOT` to point at the simulator's root, ie
+/// `~/.chia/simulator/main`.
+pub struct SimulatorClient {
+    fullnode: FullnodeClient,
+}
+
+impl ServiceClient for SimulatorClient {
+    const SERVICE: ChiaService = ChiaService::FullNode;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        SimulatorClient {
+            fullnode: FullnodeClient::from_transport(transport),
+        }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        self.fullnode.transport()
+    }
+}
+
+client_methods!(SimulatorClient);
+
+impl Deref for SimulatorClient {
+    type Target = FullnodeClient;
+    fn deref(&self) -> &FullnodeClient {
+        &self.fullnode
+    }
+}
+
+impl From<FullnodeClient> for SimulatorClient {
+    fn from(fullnode: FullnodeClient) -> Self {
+        SimulatorClient { fullnode }
+    }
+}
+
+impl SimulatorClient {
+    pub fn into_fullnode(self) -> FullnodeClient {
+        self.fullnode
+    }
+    /// Farms `blocks` blocks paying the rewards to `address` and returns the new peak height.
+    pub async fn farm_block(&self, address: &str, blocks: u32) -> Result<u32, ChiaRpcError> {
+        self.farm(address, blocks, false).await
+    }
+    /// Like [`Self::farm_block`] but the last block farmed is a transaction block, so pending
+    /// mempool items are included.
+    pub async fn farm_tx_block(&self, address: &str, blocks: u32) -> Result<u32, ChiaRpcError> {
+        self.farm(address, blocks, true).await
+    }
+    async fn farm(
+        &self,
+        address: &str,
+        blocks: u32,
+        guarantee_tx_block: bool,
+    ) -> Result<u32, ChiaRpcError> {
+        let data = FarmBlock {
+            address,
+            blocks,
+            guarantee_tx_block,
+        };
+        Ok(request(self.transport().as_ref(), &data)
+            .await?
+            .new_peak_height)
+    }
+    /// When enabled the simulator farms a block whenever a transaction enters the mempool.
+    pub async fn set_auto_farming(&self, auto_farm: bool) -> Result<bool, ChiaRpcError> {
+        Ok(
+            request(self.transport().as_ref(), &SetAutoFarming { auto_farm })
+                .await?
+                .auto_farm_enabled,
+        )
+    }
+    pub async fn get_auto_farming(&self) -> Result<bool, ChiaRpcError> {
+        Ok(request(self.transport().as_ref(), &GetAutoFarming {})
+            .await?
+            .auto_farm_enabled)
+    }
+    pub async fn get_farming_ph(&self) -> Result<Bytes32, ChiaRpcError> {
+        Ok(request(self.transport().as_ref(), &GetFarmingPh {})
+            .await?
+            .puzzle_hash)
+    }
+    /// Removes the last `num_of_blocks` blocks, or every block after genesis with
+    /// `delete_all_blocks`, and returns the new peak height.
+    pub async fn revert_blocks(
+        &self,
+        num_of_blocks: u32,
+        delete_all_blocks: bool,
+    ) -> Result<u32, ChiaRpcError> {
+        let data = RevertBlocks {
+            num_of_blocks,
+            delete_all_blocks,
+        };
+        Ok(request(self.transport().as_ref(), &data)
+            .await?
+            .new_peak_height)
+    }
+    /// Replaces the last `num_of_blocks_to_rev` blocks with `num_of_new_blocks` new ones and
+    /// returns the new peak height.
+    pub async fn reorg_blocks(
+        &self,
+        num_of_blocks_to_rev: u32,
+        num_of_new_blocks: u32,
+        revert_all_blocks: bool,
+    ) -> Result<u32, ChiaRpcError> {
+        let data = ReorgBlocks {
+            num_of_blocks_to_rev,
+            num_of_new_blocks,
+            revert_all_blocks,
+        };
+        Ok(request(self.transport().as_ref(), &data)
+            .await?
+            .new_peak_height)
+    }
+    pub async fn get_all_coins(
+        &self,
+        include_spent_coins: bool,
+    ) -> Result<Vec<CoinRecord>, ChiaRpcError> {
+        let data = GetAllCoins {
+            include_spent_coins,
+        };
+        Ok(request(self.transport().as_ref(), &data)
+            .await?
+            .coin_records)
+    }
+    /// Every puzzle hash holding coins, mapped to its total amount and number of coins.
+    pub async fn get_all_puzzle_hashes(&self) -> Result<HashMap<String, (u64, u64)>, ChiaRpcError> {
+        Ok(request(self.transport().as_ref(), &GetAllPuzzleHashes {})
+            .await?
+            .puzzle_hashes)
+    }
+}
@@ -0,0 +1,177 @@
+use crate::clients::error::ChiaRpcError;
+use rustls::client::{
+    verify_server_cert_signed_by_trust_anchor, ServerCertVerified, ServerCertVerifier,
+};
+use rustls::server::ParsedCertificate;
+use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerName};
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChiaService {
+    Daemon,
+    FullNode,
+    Wallet,
+    Farmer,
+    Harvester,
+    DataLayer,
+    Crawler,
+    Timelord,
+}
+
+impl ChiaService {
+    /// Name used by Chia for the service's directory under `config/ssl`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChiaService::Daemon => "daemon",
+            ChiaService::FullNode => "full_node",
+            ChiaService::Wallet => "wallet",
+            ChiaService::Farmer => "farmer",
+            ChiaService::Harvester => "harvester",
+            ChiaService::DataLayer => "data_layer",
+            ChiaService::Crawler => "crawler",
+            ChiaService::Timelord => "timelord",
+        }
+    }
+    /// Name the daemon uses for the service in `start_service` and `is_running`.
+    pub fn daemon_name(&self) -> &'static str {
+        match self {
+            ChiaService::Daemon => "daemon",
+            ChiaService::FullNode => "chia_full_node",
+            ChiaService::Wallet => "chia_wallet",
+            ChiaService::Farmer => "chia_farmer",
+            ChiaService::Harvester => "chia_harvester",
+            ChiaService::DataLayer => "chia_data_layer",
+            ChiaService::Crawler => "chia_crawler",
+            ChiaService::Timelord => "chia_timelord",
+        }
+    }
+    pub fn default_rpc_port(&self) -> u32 {
+        match self {
+            ChiaService::Daemon => 55400,
+            ChiaService::FullNode => 8555,
+            ChiaService::Wallet => 9256,
+            ChiaService::Farmer => 8559,
+            ChiaService::Harvester => 8560,
+            ChiaService::DataLayer => 8562,
+            ChiaService::Crawler => 8561,
+            ChiaService::Timelord => 8557,
+        }
+    }
+}
+
+/// Paths of the private certificate pair for a service and the private CA that signed it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SslFiles {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    pub ca: PathBuf,
+}
+
+impl SslFiles {
+    /// Follows the standard layout, ie `{ssl_path}/full_node/private_full_node.crt`.
+    pub fn for_service<P: AsRef<Path>>(ssl_path: P, service: ChiaService) -> Self {
+        let ssl_path = ssl_path.as_ref();
+        let name = service.name();
+        let service_dir = ssl_path.join(name);
+        SslFiles {
+            cert: service_dir.join(format!("private_{}.crt", name)),
+            key: service_dir.join(format!("private_{}.key", name)),
+            ca: ssl_path.join("ca").join("private_ca.crt"),
+        }
+    }
+
+    /// Builds a TLS config presenting the service certificate and trusting only the private CA.
+    pub fn client_config(&self) -> Result<ClientConfig, ChiaRpcError> {
+        let mut roots = RootCertStore::empty();
+        for ca in load_certs(&self.ca)? {
+            roots.add(&ca).map_err(|e| {
+                ChiaRpcError::Config(format!(
+                    "Invalid CA Certificate {}: {:?}",
+                    self.ca.display(),
+                    e
+                ))
+            })?;
+        }
+        ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(PrivateCaVerifier { roots }))
+            .with_client_auth_cert(load_certs(&self.cert)?, load_key(&self.key)?)
+            .map_err(|e| {
+                ChiaRpcError::Config(format!(
+                    "Failed to load Identity from {} and {}: {:?}",
+                    self.cert.display(),
+                    self.key.display(),
+                    e
+                ))
+            })
+    }
+}
+
+/// Chia issues every service certificate for `chia.net`, so like the Chia clients we only
+/// verify the chain against the private CA and skip the hostname check.
+struct PrivateCaVerifier {
+    roots: RootCertStore,
+}
+
+impl ServerCertVerifier for PrivateCaVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let cert = ParsedCertificate::try_from(end_entity)?;
+        verify_server_cert_signed_by_trust_anchor(&cert, &self.roots, intermediates, now)?;
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, ChiaRpcError> {
+    fs::read(path)
+        .map_err(|e| ChiaRpcError::Config(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+pub(crate) fn load_certs(path: &Path) -> Result<Vec<Certificate>, ChiaRpcError> {
+    let certs =
+        rustls_pemfile::certs(&mut BufReader::new(read_file(path)?.as_slice())).map_err(|e| {
+            ChiaRpcError::Config(format!(
+                "Failed to parse Certificate file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+    if certs.is_empty() {
+        return Err(ChiaRpcError::Config(format!(
+            "No Certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+pub(crate) fn load_key(path: &Path) -> Result<PrivateKey, ChiaRpcError> {
+    let items = rustls_pemfile::read_all(&mut BufReader::new(read_file(path)?.as_slice()))
+        .map_err(|e| {
+            ChiaRpcError::Config(format!(
+                "Failed to parse Key file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| ChiaRpcError::Config(format!("No Private Key found in {}", path.display())))
+}
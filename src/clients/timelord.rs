@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use crate::clients::builder::{client_methods, ServiceClient};
+use crate::clients::ssl::ChiaService;
+use crate::clients::transport::RpcTransport;
+
+/// The timelord only serves the endpoints common to every service, see
+/// [`RpcService`](crate::clients::service::RpcService).
+pub struct TimelordClient {
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl ServiceClient for TimelordClient {
+    const SERVICE: ChiaService = ChiaService::Timelord;
+    fn from_transport(transport: Arc<dyn RpcTransport>) -> Self {
+        TimelordClient { transport }
+    }
+    fn transport(&self) -> &Arc<dyn RpcTransport> {
+        &self.transport
+    }
+}
+
+client_methods!(TimelordClient);
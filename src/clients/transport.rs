@@ -0,0 +1,124 @@
+use crate::clients::common::{get_url, post_json};
+use crate::clients::error::ChiaRpcError;
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Sends a JSON body to a named RPC endpoint, ie `get_blockchain_state`, and returns the JSON reply.
+#[async_trait]
+pub trait RpcTransport: Send + Sync {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError>;
+}
+
+pub struct ReqwestTransport {
+    client: Client,
+    base_url: String,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client, base_url: String) -> Self {
+        ReqwestTransport { client, base_url }
+    }
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[async_trait]
+impl RpcTransport for ReqwestTransport {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        post_json(&self.client, &get_url(&self.base_url, endpoint), body).await
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    pub endpoint: String,
+    pub body: Value,
+}
+
+/// In memory transport serving scripted responses per endpoint and recording every request.
+#[derive(Default)]
+pub struct MockTransport {
+    queued: Mutex<HashMap<String, VecDeque<Result<Value, ChiaRpcError>>>>,
+    fixed: Mutex<HashMap<String, Value>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns `response` for every call to `endpoint` once the queued responses run out.
+    pub fn respond(&self, endpoint: &str, response: Value) -> &Self {
+        self.fixed
+            .lock()
+            .unwrap()
+            .insert(endpoint.to_string(), response);
+        self
+    }
+    /// Queues a response for the next call to `endpoint`.
+    pub fn respond_once(&self, endpoint: &str, response: Value) -> &Self {
+        self.enqueue(endpoint, Ok(response))
+    }
+    /// Queues a failure for the next call to `endpoint`.
+    pub fn fail_once(&self, endpoint: &str, error: ChiaRpcError) -> &Self {
+        self.enqueue(endpoint, Err(error))
+    }
+    fn enqueue(&self, endpoint: &str, response: Result<Value, ChiaRpcError>) -> &Self {
+        self.queued
+            .lock()
+            .unwrap()
+            .entry(endpoint.to_string())
+            .or_default()
+            .push_back(response);
+        self
+    }
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+    pub fn requests_to(&self, endpoint: &str) -> Vec<Value> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.endpoint == endpoint)
+            .map(|r| r.body.clone())
+            .collect()
+    }
+    pub fn clear_requests(&self) {
+        self.requests.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl RpcTransport for MockTransport {
+    async fn send(&self, endpoint: &str, body: &Value) -> Result<Value, ChiaRpcError> {
+        self.requests.lock().unwrap().push(RecordedRequest {
+            endpoint: endpoint.to_string(),
+            body: body.clone(),
+        });
+        let queued = self
+            .queued
+            .lock()
+            .unwrap()
+            .get_mut(endpoint)
+            .and_then(|q| q.pop_front());
+        match queued {
+            Some(response) => response,
+            None => match self.fixed.lock().unwrap().get(endpoint) {
+                Some(response) => Ok(response.clone()),
+                None => Err(ChiaRpcError::HttpStatus {
+                    url: endpoint.to_string(),
+                    status: StatusCode::NOT_FOUND,
+                    body: format!("No mock response for {}", endpoint),
+                }),
+            },
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use serde::{Deserialize, Serialize};
+
+/// Kind of a Chia peer, sent as an integer by the services.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum NodeType {
+    FullNode,
+    Harvester,
+    Farmer,
+    Timelord,
+    Introducer,
+    Wallet,
+    DataLayer,
+    Unknown(u8),
+}
+
+impl From<u8> for NodeType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => NodeType::FullNode,
+            2 => NodeType::Harvester,
+            3 => NodeType::Farmer,
+            4 => NodeType::Timelord,
+            5 => NodeType::Introducer,
+            6 => NodeType::Wallet,
+            7 => NodeType::DataLayer,
+            other => NodeType::Unknown(other),
+        }
+    }
+}
+
+impl From<NodeType> for u8 {
+    fn from(node_type: NodeType) -> Self {
+        match node_type {
+            NodeType::FullNode => 1,
+            NodeType::Harvester => 2,
+            NodeType::Farmer => 3,
+            NodeType::Timelord => 4,
+            NodeType::Introducer => 5,
+            NodeType::Wallet => 6,
+            NodeType::DataLayer => 7,
+            NodeType::Unknown(other) => other,
+        }
+    }
+}
+
+/// A peer of a service, from `get_connections`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PeerConnection {
+    pub node_id: Bytes32,
+    pub peer_host: String,
+    pub peer_port: u16,
+    /// Port the peer listens on, `peer_port` is the outgoing port of inbound connections.
+    pub peer_server_port: u16,
+    #[serde(rename = "type")]
+    pub node_type: NodeType,
+    pub local_port: u16,
+    pub bytes_read: Option<u64>,
+    pub bytes_written: Option<u64>,
+    pub creation_time: f64,
+    pub last_message_time: f64,
+    /// Only set for full node peers.
+    pub peak_hash: Option<Bytes32>,
+    pub peak_height: Option<u32>,
+    pub peak_weight: Option<u128>,
+}
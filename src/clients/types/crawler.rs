@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PeerCounts {
+    pub total_last_5_days: u64,
+    pub reliable_nodes: u64,
+    pub ipv4_last_5_days: u64,
+    pub ipv6_last_5_days: u64,
+    /// Number of peers by reported protocol version.
+    pub versions: HashMap<String, u64>,
+}
+
+/// One page of the peers the crawler saw since a timestamp.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PeerIps {
+    pub ips: Vec<String>,
+    /// Number of peers matching, across all pages.
+    pub total: u64,
+}
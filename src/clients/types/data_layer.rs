@@ -0,0 +1,176 @@
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Arbitrary bytes sent as a `0x` prefixed hex string, used for DataLayer keys and values.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HexBytes(pub Vec<u8>);
+
+impl HexBytes {
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(format!("Invalid hex digits in {}", hex));
+        }
+        if !hex.len().is_multiple_of(2) {
+            return Err(format!("Invalid hex string {}", hex));
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                u8::from_str_radix(&hex[i..i + 2], 16)
+                    .map_err(|_| format!("Invalid hex digits in {}", hex))
+            })
+            .collect::<Result<Vec<u8>, String>>()
+            .map(HexBytes)
+    }
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for HexBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x")?;
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<u8>> for HexBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        HexBytes(bytes)
+    }
+}
+
+impl From<&[u8]> for HexBytes {
+    fn from(bytes: &[u8]) -> Self {
+        HexBytes(bytes.to_vec())
+    }
+}
+
+impl From<&str> for HexBytes {
+    /// Takes the UTF-8 bytes of the string, use [`HexBytes::from_hex`] to parse hex.
+    fn from(text: &str) -> Self {
+        HexBytes(text.as_bytes().to_vec())
+    }
+}
+
+impl Serialize for HexBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for HexBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        HexBytes::from_hex(&hex).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct KeyValue {
+    pub hash: Bytes32,
+    pub key: HexBytes,
+    pub value: HexBytes,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Root {
+    pub hash: Bytes32,
+    pub confirmed: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RootHistoryEntry {
+    pub root_hash: Bytes32,
+    pub confirmed: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DiffType {
+    Insert,
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct KvDiff {
+    #[serde(rename = "type")]
+    pub diff_type: DiffType,
+    pub key: HexBytes,
+    pub value: HexBytes,
+}
+
+/// One change of a `batch_update`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum Change {
+    Insert { key: HexBytes, value: HexBytes },
+    Delete { key: HexBytes },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Mirror {
+    pub coin_id: Bytes32,
+    pub launcher_id: Bytes32,
+    pub amount: u64,
+    pub urls: Vec<String>,
+    pub ours: bool,
+    pub confirmed_at_height: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DataStoreSyncStatus {
+    pub root_hash: Bytes32,
+    pub generation: u32,
+    pub target_root_hash: Bytes32,
+    pub target_generation: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes = HexBytes::from_hex("0x00ffAb").unwrap();
+        assert_eq!(bytes.as_bytes(), &[0x00, 0xff, 0xab]);
+        assert_eq!(bytes.to_string(), "0x00ffab");
+        assert_eq!(HexBytes::from_hex("00ffab").unwrap(), bytes);
+        assert_eq!(HexBytes::from_hex("0x").unwrap(), HexBytes::default());
+    }
+
+    #[test]
+    fn invalid_hex_is_rejected() {
+        assert!(HexBytes::from_hex("0xabc").is_err());
+        assert!(HexBytes::from_hex("0xzz").is_err());
+        assert!(HexBytes::from_hex("0xé0").is_err());
+        assert!(HexBytes::from_hex("+f").is_err());
+        assert!(HexBytes::from_hex("0x+f").is_err());
+        assert!(HexBytes::from_hex("0x-f").is_err());
+    }
+
+    #[test]
+    fn text_is_encoded_as_utf8() {
+        assert_eq!(HexBytes::from("hi").to_string(), "0x6869");
+    }
+
+    #[test]
+    fn serde_uses_hex_strings() {
+        let bytes = HexBytes(vec![1, 2]);
+        assert_eq!(serde_json::to_value(&bytes).unwrap(), json!("0x0102"));
+        assert_eq!(
+            serde_json::from_value::<HexBytes>(json!("0x0102")).unwrap(),
+            bytes
+        );
+        assert!(serde_json::from_value::<HexBytes>(json!("0x1")).is_err());
+    }
+}
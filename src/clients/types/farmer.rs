@@ -0,0 +1,66 @@
+use druid_garden_chia_types::blockchain::proof_of_space::ProofOfSpace;
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NewSignagePoint {
+    pub challenge_hash: Bytes32,
+    pub challenge_chain_sp: Bytes32,
+    pub reward_chain_sp: Bytes32,
+    pub difficulty: u64,
+    pub sub_slot_iters: u64,
+    pub signage_point_index: u32,
+    pub peak_height: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NewFarmingInfo {
+    pub challenge_hash: Bytes32,
+    pub signage_point: Bytes32,
+    pub passed_filter: u32,
+    pub proofs: u32,
+    pub total_plots: u32,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct FarmerSignagePoint {
+    pub signage_point: NewSignagePoint,
+    /// Proofs found for the signage point, paired with the plot identifier.
+    pub proofs: Vec<(String, ProofOfSpace)>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RewardTargets {
+    pub farmer_target: String,
+    pub pool_target: String,
+    pub have_farmer_sk: Option<bool>,
+    pub have_pool_sk: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PoolConfig {
+    pub launcher_id: Bytes32,
+    pub pool_url: String,
+    pub payout_instructions: String,
+    pub target_puzzle_hash: Bytes32,
+    pub p2_singleton_puzzle_hash: Bytes32,
+    pub owner_public_key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PoolState {
+    pub p2_singleton_puzzle_hash: Bytes32,
+    pub points_found_since_start: u64,
+    pub points_found_24h: Vec<(f64, u64)>,
+    pub points_acknowledged_since_start: u64,
+    pub points_acknowledged_24h: Vec<(f64, u64)>,
+    pub next_farmer_update: f64,
+    pub next_pool_info_update: f64,
+    pub current_points: u64,
+    pub current_difficulty: Option<u64>,
+    pub pool_errors_24h: Vec<(f64, serde_json::Value)>,
+    pub authentication_token_timeout: Option<u8>,
+    pub plot_count: Option<u32>,
+    pub pool_config: PoolConfig,
+}
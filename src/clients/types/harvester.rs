@@ -0,0 +1,309 @@
+use druid_garden_chia_types::blockchain::sized_bytes::{Bytes32, Bytes48};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlotInfo {
+    pub filename: String,
+    pub size: u8,
+    pub plot_id: Bytes32,
+    pub pool_public_key: Option<Bytes48>,
+    pub pool_contract_puzzle_hash: Option<Bytes32>,
+    /// Combined key of the farmer and the plot's local key.
+    pub plot_public_key: Bytes48,
+    /// Only reported by some harvester versions.
+    pub farmer_public_key: Option<Bytes48>,
+    pub file_size: u64,
+    pub time_modified: f64,
+    pub compression_level: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HarvesterConnection {
+    pub node_id: Bytes32,
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HarvesterSyncing {
+    pub initial: bool,
+    pub plot_files_processed: u32,
+    pub plot_files_total: u32,
+}
+
+/// Every plot a harvester reported to the farmer, from `get_harvesters`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HarvesterDetails {
+    pub connection: HarvesterConnection,
+    pub plots: Vec<PlotInfo>,
+    pub failed_to_open_filenames: Vec<String>,
+    pub no_key_filenames: Vec<String>,
+    pub duplicates: Vec<String>,
+    pub total_plot_size: u64,
+    pub syncing: Option<HarvesterSyncing>,
+    pub last_sync_time: Option<f64>,
+}
+
+/// Plot counts of a harvester, from `get_harvesters_summary`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HarvesterSummary {
+    pub connection: HarvesterConnection,
+    pub plots: u64,
+    pub failed_to_open_filenames: u64,
+    pub no_key_filenames: u64,
+    pub duplicates: u64,
+    pub total_plot_size: u64,
+    pub syncing: Option<HarvesterSyncing>,
+    pub last_sync_time: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlotFilter {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+/// One page of the plots a harvester reported, `T` is [`PlotInfo`] or a filename.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PaginatedPlots<T> {
+    pub node_id: Bytes32,
+    pub page: u32,
+    pub page_count: u32,
+    pub total_count: u32,
+    pub plots: Vec<T>,
+}
+
+/// The plots loaded by a harvester, from `get_plots`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HarvesterPlots {
+    pub plots: Vec<PlotInfo>,
+    pub failed_to_open_filenames: Vec<String>,
+    /// Plots whose farmer or pool key is not in the keychain.
+    #[serde(alias = "not_found_filenames")]
+    pub no_key_filenames: Vec<String>,
+}
+
+/// Settings of `get_harvester_config` and `update_harvester_config`, unset fields are left as is
+/// on update.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct HarvesterConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_gpu_harvesting: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_gpu_index: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_cpu_affinity: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_decompressor_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decompressor_thread_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recursive_plot_scan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_parameter_interval_seconds: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotIssue {
+    /// `host:port` of the harvester, when known.
+    pub harvester: Option<String>,
+    pub filename: String,
+}
+
+/// Plots that are not farming, gathered from one or more harvesters.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PlotIssues {
+    pub failed_to_open: Vec<PlotIssue>,
+    pub no_key: Vec<PlotIssue>,
+    /// Every copy of a plot id that is loaded more than once, across all the harvesters given.
+    pub duplicates: Vec<PlotIssue>,
+}
+
+impl PlotIssues {
+    pub fn from_plots(plots: &HarvesterPlots) -> Self {
+        let mut issues = PlotIssues::default();
+        issues.add(
+            None,
+            &plots.failed_to_open_filenames,
+            &plots.no_key_filenames,
+        );
+        issues.duplicates = find_duplicates(plots.plots.iter().map(|plot| (None, plot)));
+        issues
+    }
+    pub fn from_harvesters(harvesters: &[HarvesterDetails]) -> Self {
+        let mut issues = PlotIssues::default();
+        for harvester in harvesters {
+            issues.add(
+                Some(harvester.connection.address()),
+                &harvester.failed_to_open_filenames,
+                &harvester.no_key_filenames,
+            );
+        }
+        issues.duplicates = find_duplicates(harvesters.iter().flat_map(|harvester| {
+            let address = harvester.connection.address();
+            harvester
+                .plots
+                .iter()
+                .map(move |plot| (Some(address.clone()), plot))
+        }));
+        for harvester in harvesters {
+            for filename in &harvester.duplicates {
+                let issue = PlotIssue {
+                    harvester: Some(harvester.connection.address()),
+                    filename: filename.clone(),
+                };
+                if !issues.duplicates.contains(&issue) {
+                    issues.duplicates.push(issue);
+                }
+            }
+        }
+        issues
+    }
+    pub fn len(&self) -> usize {
+        self.failed_to_open.len() + self.no_key.len() + self.duplicates.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn add(&mut self, harvester: Option<String>, failed_to_open: &[String], no_key: &[String]) {
+        let issue = |filename: &String| PlotIssue {
+            harvester: harvester.clone(),
+            filename: filename.clone(),
+        };
+        self.failed_to_open.extend(failed_to_open.iter().map(issue));
+        self.no_key.extend(no_key.iter().map(issue));
+    }
+}
+
+impl HarvesterConnection {
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+fn find_duplicates<'a, I>(plots: I) -> Vec<PlotIssue>
+where
+    I: Iterator<Item = (Option<String>, &'a PlotInfo)>,
+{
+    let mut by_id: HashMap<&Bytes32, Vec<PlotIssue>> = HashMap::new();
+    let mut order = vec![];
+    for (harvester, plot) in plots {
+        let copies = by_id.entry(&plot.plot_id).or_insert_with(|| {
+            order.push(&plot.plot_id);
+            vec![]
+        });
+        copies.push(PlotIssue {
+            harvester,
+            filename: plot.filename.clone(),
+        });
+    }
+    order
+        .into_iter()
+        .filter_map(|id| by_id.remove(id))
+        .filter(|copies| copies.len() > 1)
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn plot(filename: &str, id: u8) -> Value {
+        json!({
+            "filename": filename,
+            "size": 32,
+            "plot_id": format!("0x{}", format!("{:02x}", id).repeat(32)),
+            "pool_public_key": null,
+            "pool_contract_puzzle_hash": null,
+            "plot_public_key": format!("0x{}", "ab".repeat(48)),
+            "farmer_public_key": null,
+            "file_size": 108_000_000_000u64,
+            "time_modified": 0.0,
+            "compression_level": null,
+        })
+    }
+
+    fn harvester(host: &str, plots: Vec<Value>, duplicates: &[&str]) -> HarvesterDetails {
+        serde_json::from_value(json!({
+            "connection": {
+                "node_id": format!("0x{}", "00".repeat(32)),
+                "host": host,
+                "port": 8448,
+            },
+            "plots": plots,
+            "failed_to_open_filenames": [format!("{}-broken.plot", host)],
+            "no_key_filenames": [],
+            "duplicates": duplicates,
+            "total_plot_size": 0,
+            "syncing": null,
+            "last_sync_time": null,
+        }))
+        .unwrap()
+    }
+
+    fn issue(harvester: &str, filename: &str) -> PlotIssue {
+        PlotIssue {
+            harvester: Some(harvester.to_string()),
+            filename: filename.to_string(),
+        }
+    }
+
+    #[test]
+    fn single_harvester_issues() {
+        let plots: HarvesterPlots = serde_json::from_value(json!({
+            "plots": [plot("a.plot", 1), plot("b.plot", 2), plot("c.plot", 1)],
+            "failed_to_open_filenames": ["broken.plot"],
+            "not_found_filenames": ["nokey.plot"],
+        }))
+        .unwrap();
+        let issues = PlotIssues::from_plots(&plots);
+        let names = |issues: &[PlotIssue]| -> Vec<String> {
+            issues.iter().map(|i| i.filename.clone()).collect()
+        };
+        assert_eq!(names(&issues.failed_to_open), vec!["broken.plot"]);
+        assert_eq!(names(&issues.no_key), vec!["nokey.plot"]);
+        assert_eq!(names(&issues.duplicates), vec!["a.plot", "c.plot"]);
+        assert_eq!(issues.len(), 4);
+    }
+
+    #[test]
+    fn duplicates_across_harvesters() {
+        let harvesters = [
+            harvester("one", vec![plot("a.plot", 1), plot("b.plot", 2)], &[]),
+            harvester("two", vec![plot("a2.plot", 1)], &["x.plot"]),
+        ];
+        let issues = PlotIssues::from_harvesters(&harvesters);
+        assert_eq!(
+            issues.failed_to_open,
+            vec![
+                issue("one:8448", "one-broken.plot"),
+                issue("two:8448", "two-broken.plot")
+            ]
+        );
+        assert!(issues.no_key.is_empty());
+        assert_eq!(
+            issues.duplicates,
+            vec![
+                issue("one:8448", "a.plot"),
+                issue("two:8448", "a2.plot"),
+                issue("two:8448", "x.plot"),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_issues() {
+        let plots: HarvesterPlots = serde_json::from_value(json!({
+            "plots": [plot("a.plot", 1)],
+            "failed_to_open_filenames": [],
+            "no_key_filenames": [],
+        }))
+        .unwrap();
+        assert!(PlotIssues::from_plots(&plots).is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod connection;
+pub mod crawler;
+pub mod data_layer;
+pub mod farmer;
+pub mod harvester;
@@ -1 +1,3 @@
 pub mod clients;
+#[cfg(feature = "test-support")]
+pub mod test_support;
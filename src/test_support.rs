@@ -0,0 +1,225 @@
+//! In process Chia RPC server for integration tests, enabled with the `test-support` feature.
+//!
+//! The server generates a throwaway private CA and a certificate pair for every service in the
+//! standard `ssl` layout, requires clients to present one of them, and answers each route with a
+//! canned JSON response while recording the requests it receives.
+use crate::clients::builder::{RpcClientBuilder, ServiceClient};
+use crate::clients::error::ChiaRpcError;
+use crate::clients::ssl::{load_certs, load_key, ChiaService, SslFiles};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::{Body, Request, Response, StatusCode};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, DnType, IsCa, KeyUsagePurpose};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{RootCertStore, ServerConfig};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+
+const SERVICES: [ChiaService; 8] = [
+    ChiaService::Daemon,
+    ChiaService::FullNode,
+    ChiaService::Wallet,
+    ChiaService::Farmer,
+    ChiaService::Harvester,
+    ChiaService::DataLayer,
+    ChiaService::Crawler,
+    ChiaService::Timelord,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerRequest {
+    /// Request path without the leading slash, ie `get_blockchain_state`.
+    pub route: String,
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct ServerState {
+    responses: Mutex<HashMap<String, (StatusCode, String)>>,
+    requests: Mutex<Vec<ServerRequest>>,
+}
+
+pub struct MockChiaServer {
+    addr: SocketAddr,
+    ssl_dir: TempDir,
+    state: Arc<ServerState>,
+    task: JoinHandle<()>,
+}
+
+impl MockChiaServer {
+    /// Starts a server on a random localhost port presenting the certificate of `service`.
+    pub async fn start(service: ChiaService) -> Result<Self, ChiaRpcError> {
+        let ssl_dir = TempDir::new().map_err(|e| config_error("create ssl directory", e))?;
+        generate_ssl(ssl_dir.path())?;
+        let acceptor = TlsAcceptor::from(Arc::new(server_config(ssl_dir.path(), service)?));
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| config_error("bind", e))?;
+        let addr = listener.local_addr().map_err(|e| config_error("bind", e))?;
+        let state = Arc::new(ServerState::default());
+        let server_state = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let state = server_state.clone();
+                tokio::spawn(async move {
+                    if let Ok(stream) = acceptor.accept(stream).await {
+                        let service = service_fn(move |req| handle(state.clone(), req));
+                        let _ = Http::new().serve_connection(stream, service).await;
+                    }
+                });
+            }
+        });
+        Ok(MockChiaServer {
+            addr,
+            ssl_dir,
+            state,
+            task,
+        })
+    }
+    pub fn host(&self) -> String {
+        self.addr.ip().to_string()
+    }
+    pub fn port(&self) -> u32 {
+        self.addr.port() as u32
+    }
+    /// Root of the generated `ssl` layout, usable as the `ssl_path` of any client.
+    pub fn ssl_path(&self) -> &Path {
+        self.ssl_dir.path()
+    }
+    /// Builder already pointed at this server with the generated certificates.
+    pub fn builder<C: ServiceClient>(&self) -> RpcClientBuilder<C> {
+        RpcClientBuilder::new()
+            .host(&self.host())
+            .port(self.port())
+            .ssl_files(SslFiles::for_service(self.ssl_path(), C::SERVICE))
+    }
+    pub fn client<C: ServiceClient>(&self) -> Result<C, ChiaRpcError> {
+        self.builder().build()
+    }
+    /// Answers every request to `route` with a 200 and `response` as the body.
+    pub fn respond(&self, route: &str, response: Value) -> &Self {
+        self.respond_with(route, StatusCode::OK, response.to_string())
+    }
+    pub fn respond_with(&self, route: &str, status: StatusCode, body: String) -> &Self {
+        self.state
+            .responses
+            .lock()
+            .unwrap()
+            .insert(route.trim_start_matches('/').to_string(), (status, body));
+        self
+    }
+    pub fn requests(&self) -> Vec<ServerRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+    pub fn last_request(&self) -> Option<ServerRequest> {
+        self.state.requests.lock().unwrap().last().cloned()
+    }
+}
+
+impl Drop for MockChiaServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle(state: Arc<ServerState>, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let route = req.uri().path().trim_start_matches('/').to_string();
+    let bytes = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+    let body = serde_json::from_slice(&bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).to_string()));
+    state.requests.lock().unwrap().push(ServerRequest {
+        route: route.clone(),
+        body,
+    });
+    let (status, body) = state
+        .responses
+        .lock()
+        .unwrap()
+        .get(&route)
+        .cloned()
+        .unwrap_or_else(|| (StatusCode::NOT_FOUND, "404: Not Found".to_string()));
+    Ok(Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .unwrap_or_default())
+}
+
+/// TLS settings of a server presenting the certificate of `service` from `ssl_path` and only
+/// accepting clients with a certificate signed by the same private CA.
+pub fn server_config(ssl_path: &Path, service: ChiaService) -> Result<ServerConfig, ChiaRpcError> {
+    let ssl_files = SslFiles::for_service(ssl_path, service);
+    let mut roots = RootCertStore::empty();
+    for ca in load_certs(&ssl_files.ca)? {
+        roots.add(&ca).map_err(|e| config_error("add CA", e))?;
+    }
+    ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        .with_single_cert(load_certs(&ssl_files.cert)?, load_key(&ssl_files.key)?)
+        .map_err(|e| config_error("build server config", e))
+}
+
+/// Writes a private CA and a `chia.net` certificate pair per service like `chia init` does.
+pub fn generate_ssl(ssl_path: &Path) -> Result<(), ChiaRpcError> {
+    let mut ca_params = CertificateParams::new(vec![]);
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, "Chia CA");
+    ca_params
+        .distinguished_name
+        .push(DnType::OrganizationName, "Chia");
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    let ca = Certificate::from_params(ca_params).map_err(|e| config_error("generate CA", e))?;
+    let ca_files = SslFiles::for_service(ssl_path, ChiaService::Daemon);
+    write_pem(
+        &ca_files.ca,
+        ca.serialize_pem()
+            .map_err(|e| config_error("serialize CA", e))?,
+    )?;
+    write_pem(
+        &ca_files.ca.with_extension("key"),
+        ca.serialize_private_key_pem(),
+    )?;
+    for service in SERVICES {
+        let mut params = CertificateParams::new(vec!["chia.net".to_string()]);
+        params.distinguished_name.push(DnType::CommonName, "Chia");
+        params
+            .distinguished_name
+            .push(DnType::OrganizationName, "Chia");
+        let cert = Certificate::from_params(params)
+            .map_err(|e| config_error("generate certificate", e))?;
+        let ssl_files = SslFiles::for_service(ssl_path, service);
+        write_pem(
+            &ssl_files.cert,
+            cert.serialize_pem_with_signer(&ca)
+                .map_err(|e| config_error("sign certificate", e))?,
+        )?;
+        write_pem(&ssl_files.key, cert.serialize_private_key_pem())?;
+    }
+    Ok(())
+}
+
+fn write_pem(path: &Path, contents: String) -> Result<(), ChiaRpcError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| config_error("create ssl directory", e))?;
+    }
+    fs::write(path, contents).map_err(|e| config_error("write certificate", e))
+}
+
+fn config_error<E: std::fmt::Debug>(action: &str, e: E) -> ChiaRpcError {
+    ChiaRpcError::Config(format!("Mock server failed to {}: {:?}", action, e))
+}
@@ -0,0 +1,269 @@
+//! Request correlation, reconnects and timeouts of the daemon client against an in process
+//! WebSocket server using the certificates of the `test-support` feature.
+use druid_garden_chia_rpc::clients::daemon::{DaemonClient, WsMessage};
+use druid_garden_chia_rpc::clients::error::ChiaRpcError;
+use druid_garden_chia_rpc::clients::events::ChiaEvent;
+use druid_garden_chia_rpc::clients::ssl::{ChiaService, SslFiles};
+use druid_garden_chia_rpc::test_support::{generate_ssl, server_config};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+type ServerStream = WebSocketStream<TlsStream<TcpStream>>;
+
+struct MockDaemon {
+    url: String,
+    ssl_dir: TempDir,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockDaemon {
+    /// Runs `handler` for every connection, with the number of the connection from 0.
+    async fn start<F, Fut>(handler: F) -> Self
+    where
+        F: Fn(usize, ServerStream) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::start_blackholing_after(usize::MAX, handler).await
+    }
+    /// Connections from the `handshakes`th on are accepted but never answered.
+    async fn start_blackholing_after<F, Fut>(handshakes: usize, handler: F) -> Self
+    where
+        F: Fn(usize, ServerStream) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let ssl_dir = TempDir::new().unwrap();
+        generate_ssl(ssl_dir.path()).unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(
+            server_config(ssl_dir.path(), ChiaService::Daemon).unwrap(),
+        ));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("wss://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let count = connections.clone();
+        let handler = Arc::new(handler);
+        tokio::spawn(async move {
+            let mut blackholed = vec![];
+            while let Ok((stream, _)) = listener.accept().await {
+                let index = count.fetch_add(1, Ordering::SeqCst);
+                if index >= handshakes {
+                    blackholed.push(stream);
+                    continue;
+                }
+                let (acceptor, handler) = (acceptor.clone(), handler.clone());
+                tokio::spawn(async move {
+                    let Ok(stream) = acceptor.accept(stream).await else {
+                        return;
+                    };
+                    if let Ok(ws) = tokio_tungstenite::accept_async(stream).await {
+                        handler(index, ws).await;
+                    }
+                });
+            }
+        });
+        MockDaemon {
+            url,
+            ssl_dir,
+            connections,
+        }
+    }
+    fn ssl_files(&self) -> SslFiles {
+        SslFiles::for_service(self.ssl_dir.path(), ChiaService::Daemon)
+    }
+    async fn client(&self, timeout: Duration) -> Result<DaemonClient, ChiaRpcError> {
+        DaemonClient::connect_with_timeout(&self.url, &self.ssl_files(), timeout).await
+    }
+}
+
+async fn next_request(ws: &mut ServerStream) -> Option<WsMessage> {
+    loop {
+        match ws.next().await? {
+            Ok(Message::Text(text)) => return serde_json::from_str(&text).ok(),
+            Ok(_) => continue,
+            Err(_) => return None,
+        }
+    }
+}
+
+fn response(request: &WsMessage, data: Value) -> Message {
+    let message = WsMessage {
+        command: request.command.clone(),
+        ack: true,
+        data,
+        request_id: request.request_id.clone(),
+        destination: request.origin.clone(),
+        origin: "daemon".to_string(),
+    };
+    Message::Text(serde_json::to_string(&message).unwrap())
+}
+
+#[tokio::test]
+async fn responses_are_matched_by_request_id() {
+    let daemon = MockDaemon::start(|_, mut ws| async move {
+        let first = next_request(&mut ws).await.unwrap();
+        let second = next_request(&mut ws).await.unwrap();
+        for request in [second, first] {
+            let is_running = request.data["service"] == json!("chia_wallet");
+            let data = json!({ "success": true, "is_running": is_running });
+            ws.send(response(&request, data)).await.unwrap();
+        }
+        while next_request(&mut ws).await.is_some() {}
+    })
+    .await;
+    let client = daemon.client(Duration::from_secs(5)).await.unwrap();
+    let (node, wallet) = tokio::join!(
+        client.is_running(ChiaService::FullNode),
+        client.is_running(ChiaService::Wallet)
+    );
+    assert!(!node.unwrap());
+    assert!(wallet.unwrap());
+}
+
+#[tokio::test]
+async fn reconnects_and_registers_again_after_the_connection_drops() {
+    let received = Arc::new(Mutex::new(vec![]));
+    let server_received = received.clone();
+    let daemon = MockDaemon::start(move |index, mut ws| {
+        let received = server_received.clone();
+        async move {
+            while let Some(request) = next_request(&mut ws).await {
+                received
+                    .lock()
+                    .unwrap()
+                    .push((index, request.command.clone()));
+                let data = json!({ "success": true, "connection": index });
+                ws.send(response(&request, data)).await.unwrap();
+                if index == 0 {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+            }
+        }
+    })
+    .await;
+    let client = daemon.client(Duration::from_secs(5)).await.unwrap();
+    client.register_service("wallet_ui").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let status = client.request("get_status", json!({})).await.unwrap();
+    assert_eq!(status["connection"], json!(1));
+    assert_eq!(daemon.connections.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![
+            (0, "register_service".to_string()),
+            (1, "register_service".to_string()),
+            (1, "get_status".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn state_changed_events_registers_every_service() {
+    let registered = Arc::new(Mutex::new(vec![]));
+    let server_registered = registered.clone();
+    let daemon = MockDaemon::start(move |_, mut ws| {
+        let registered = server_registered.clone();
+        async move {
+            for _ in 0..2 {
+                let request = next_request(&mut ws).await.unwrap();
+                registered
+                    .lock()
+                    .unwrap()
+                    .push(request.data["service"].clone());
+                ws.send(response(&request, json!({ "success": true })))
+                    .await
+                    .unwrap();
+            }
+            let event = WsMessage {
+                command: "state_changed".to_string(),
+                ack: false,
+                data: json!({ "state": "coin_added", "wallet_id": 1 }),
+                request_id: String::new(),
+                destination: "wallet_ui".to_string(),
+                origin: "chia_wallet".to_string(),
+            };
+            ws.send(Message::Text(serde_json::to_string(&event).unwrap()))
+                .await
+                .unwrap();
+            while next_request(&mut ws).await.is_some() {}
+        }
+    })
+    .await;
+    let client = daemon.client(Duration::from_secs(5)).await.unwrap();
+    let events = client
+        .state_changed_events(&["wallet_ui", "metrics"])
+        .await
+        .unwrap();
+    let mut events = Box::pin(events);
+    let event = tokio::time::timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap();
+    assert!(matches!(
+        event,
+        Some(ChiaEvent::CoinAdded {
+            wallet_id: Some(1),
+            ..
+        })
+    ));
+    assert_eq!(
+        *registered.lock().unwrap(),
+        vec![json!("wallet_ui"), json!("metrics")]
+    );
+}
+
+#[tokio::test]
+async fn request_times_out_without_a_response() {
+    let daemon =
+        MockDaemon::start(
+            |_, mut ws| async move { while next_request(&mut ws).await.is_some() {} },
+        )
+        .await;
+    let client = daemon.client(Duration::from_millis(200)).await.unwrap();
+    let error = client.request("get_status", json!({})).await.unwrap_err();
+    assert!(matches!(error, ChiaRpcError::Timeout(_)), "{}", error);
+}
+
+#[tokio::test]
+async fn connect_times_out_on_a_blackholed_daemon() {
+    let daemon = MockDaemon::start_blackholing_after(0, |_, _| async {}).await;
+    let started = Instant::now();
+    let error = daemon
+        .client(Duration::from_millis(200))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, ChiaRpcError::Timeout(_)), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn reconnect_to_a_blackholed_daemon_times_out_every_request() {
+    let daemon = MockDaemon::start_blackholing_after(1, |_, mut ws| async move {
+        let request = next_request(&mut ws).await.unwrap();
+        ws.send(response(&request, json!({ "success": true })))
+            .await
+            .unwrap();
+        let _ = ws.close(None).await;
+    })
+    .await;
+    let client = daemon.client(Duration::from_millis(300)).await.unwrap();
+    client.request("get_status", json!({})).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let started = Instant::now();
+    let (first, second) = tokio::join!(
+        client.request("get_status", json!({})),
+        client.request("running_services", json!({}))
+    );
+    assert!(matches!(first, Err(ChiaRpcError::Timeout(_))));
+    assert!(matches!(second, Err(ChiaRpcError::Timeout(_))));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,118 @@
+//! Mutual TLS calls against the in process server of the `test-support` feature.
+use druid_garden_chia_rpc::clients::error::ChiaRpcError;
+use druid_garden_chia_rpc::clients::fullnode::FullnodeClient;
+use druid_garden_chia_rpc::clients::ssl::{ChiaService, SslFiles};
+use druid_garden_chia_rpc::test_support::{generate_ssl, MockChiaServer, ServerRequest};
+use serde_json::json;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn fullnode_call_over_mutual_tls() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    server.respond(
+        "get_network_info",
+        json!({ "success": true, "network_name": "testnet11", "network_prefix": "txch" }),
+    );
+    let client: FullnodeClient = server.client().unwrap();
+    let info = client.get_network_info().await.unwrap();
+    assert_eq!(info.network_name, "testnet11");
+    assert_eq!(info.network_prefix, "txch");
+    assert_eq!(
+        server.last_request(),
+        Some(ServerRequest {
+            route: "get_network_info".to_string(),
+            body: json!({}),
+        })
+    );
+}
+
+#[tokio::test]
+async fn constructors_need_no_trait_import() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    server.respond(
+        "get_routes",
+        json!({ "success": true, "routes": ["/get_routes"] }),
+    );
+    let ssl_path = server.ssl_path().to_str().unwrap();
+    let client = FullnodeClient::try_new(&server.host(), server.port(), ssl_path).unwrap();
+    let response = client.call_raw("get_routes", json!({})).await.unwrap();
+    assert_eq!(response["routes"], json!(["/get_routes"]));
+    assert_eq!(
+        server.last_request(),
+        Some(ServerRequest {
+            route: "get_routes".to_string(),
+            body: json!({}),
+        })
+    );
+}
+
+#[tokio::test]
+async fn server_receives_the_request_body() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    server.respond(
+        "get_block_record_by_height",
+        json!({ "success": true, "block_record": null }),
+    );
+    let client: FullnodeClient = server.client().unwrap();
+    client
+        .call_raw("get_block_record_by_height", json!({ "height": 7 }))
+        .await
+        .unwrap();
+    assert_eq!(
+        server.requests(),
+        vec![ServerRequest {
+            route: "get_block_record_by_height".to_string(),
+            body: json!({ "height": 7 }),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn server_rejects_a_client_without_a_certificate() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    server.respond("healthz", json!({ "success": true }));
+    let http = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let client: FullnodeClient = server.builder().client(http).build().unwrap();
+    let error = client.call_raw("healthz", json!({})).await.unwrap_err();
+    assert!(matches!(error, ChiaRpcError::Transport(_)), "{}", error);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn server_rejects_a_certificate_of_another_ca() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    server.respond("healthz", json!({ "success": true }));
+    let other = TempDir::new().unwrap();
+    generate_ssl(other.path()).unwrap();
+    let foreign = SslFiles::for_service(other.path(), ChiaService::FullNode);
+    let ssl_files = SslFiles {
+        ca: SslFiles::for_service(server.ssl_path(), ChiaService::FullNode).ca,
+        ..foreign
+    };
+    let client: FullnodeClient = server.builder().ssl_files(ssl_files).build().unwrap();
+    let error = client.call_raw("healthz", json!({})).await.unwrap_err();
+    assert!(matches!(error, ChiaRpcError::Transport(_)), "{}", error);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn deprecated_new_falls_back_without_certificates() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    let missing = server.ssl_path().join("missing");
+    let client = FullnodeClient::new(&server.host(), server.port(), missing.to_str().unwrap());
+    assert!(client.get_network_info().await.is_err());
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn unauthenticated_client_does_not_trust_the_private_ca() {
+    let server = MockChiaServer::start(ChiaService::FullNode).await.unwrap();
+    let client = FullnodeClient::new_unauthenticated(&server.host(), server.port()).unwrap();
+    let error = client.get_network_info().await.unwrap_err();
+    assert!(matches!(error, ChiaRpcError::Transport(_)), "{}", error);
+    assert!(server.requests().is_empty());
+}
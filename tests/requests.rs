@@ -0,0 +1,634 @@
+//! Golden route and body of every typed request.
+use druid_garden_chia_rpc::clients::builder::ServiceClient;
+use druid_garden_chia_rpc::clients::fullnode::FullnodeClient;
+use druid_garden_chia_rpc::clients::requests::crawler::*;
+use druid_garden_chia_rpc::clients::requests::data_layer::{
+    self, AddMirror, BatchUpdate, CreateDataStore, GetKeysValues, GetKvDiff, GetMirrors,
+    GetOwnedStores, GetRoot, GetRootHistory, GetValue, Subscribe, Unsubscribe,
+};
+use druid_garden_chia_rpc::clients::requests::farmer::*;
+use druid_garden_chia_rpc::clients::requests::fullnode::*;
+use druid_garden_chia_rpc::clients::requests::harvester::*;
+use druid_garden_chia_rpc::clients::requests::service::*;
+use druid_garden_chia_rpc::clients::requests::simulator::*;
+use druid_garden_chia_rpc::clients::requests::wallet::*;
+use druid_garden_chia_rpc::clients::requests::RpcRequest;
+use druid_garden_chia_rpc::clients::transport::MockTransport;
+use druid_garden_chia_rpc::clients::types::connection::NodeType;
+use druid_garden_chia_rpc::clients::types::data_layer::{Change, HexBytes};
+use druid_garden_chia_rpc::clients::types::harvester::{HarvesterConfig, PlotFilter};
+use druid_garden_chia_types::blockchain::sized_bytes::Bytes32;
+use druid_garden_chia_types::blockchain::spend_bundle::SpendBundle;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn hash(byte: u8) -> Bytes32 {
+    serde_json::from_value(json!(format!("0x{}", format!("{:02x}", byte).repeat(32)))).unwrap()
+}
+
+fn assert_request<R: RpcRequest>(request: &R, endpoint: &str, body: Value) {
+    assert_eq!(R::ENDPOINT, endpoint);
+    assert_eq!(serde_json::to_value(request).unwrap(), body, "{}", endpoint);
+}
+
+#[test]
+fn service_requests() {
+    let a = hash(1);
+    assert_request(
+        &GetConnections { node_type: None },
+        "get_connections",
+        json!({}),
+    );
+    assert_request(
+        &GetConnections {
+            node_type: Some(NodeType::Harvester),
+        },
+        "get_connections",
+        json!({ "node_type": 2 }),
+    );
+    assert_request(
+        &OpenConnection {
+            host: "127.0.0.1",
+            port: 8444,
+        },
+        "open_connection",
+        json!({ "host": "127.0.0.1", "port": 8444 }),
+    );
+    assert_request(
+        &CloseConnection { node_id: &a },
+        "close_connection",
+        json!({ "node_id": a }),
+    );
+    assert_request(&StopNode {}, "stop_node", json!({}));
+    assert_request(&GetRoutes {}, "get_routes", json!({}));
+    assert_request(&Healthz {}, "healthz", json!({}));
+    assert_request(&GetVersion {}, "get_version", json!({}));
+    assert_request(&GetNetworkInfo {}, "get_network_info", json!({}));
+}
+
+#[test]
+fn fullnode_requests() {
+    let (a, b) = (hash(1), hash(2));
+    assert_request(&GetBlockchainState {}, "get_blockchain_state", json!({}));
+    assert_request(
+        &GetBlock { header_hash: &a },
+        "get_block",
+        json!({ "header_hash": a }),
+    );
+    assert_request(
+        &GetBlocks {
+            start: 10,
+            end: 20,
+            exclude_header_hash: true,
+            exclude_reorged: false,
+        },
+        "get_blocks",
+        json!({ "start": 10, "end": 20, "exclude_header_hash": true }),
+    );
+    assert_request(
+        &GetBlocks {
+            start: 10,
+            end: 20,
+            exclude_header_hash: false,
+            exclude_reorged: true,
+        },
+        "get_blocks",
+        json!({ "start": 10, "end": 20, "exclude_header_hash": false, "exclude_reorged": true }),
+    );
+    assert_request(
+        &GetBlockRecordByHeight { height: 7 },
+        "get_block_record_by_height",
+        json!({ "height": 7 }),
+    );
+    assert_request(
+        &GetBlockRecord { header_hash: &a },
+        "get_block_record",
+        json!({ "header_hash": a }),
+    );
+    assert_request(
+        &GetBlockRecords { start: 1, end: 5 },
+        "get_block_records",
+        json!({ "start": 1, "end": 5 }),
+    );
+    assert_request(
+        &GetUnfinishedBlockHeaders {},
+        "get_unfinished_block_headers",
+        json!({}),
+    );
+    assert_request(
+        &GetNetworkSpace {
+            older_block_header_hash: &a,
+            newer_block_header_hash: &b,
+        },
+        "get_network_space",
+        json!({ "older_block_header_hash": a, "newer_block_header_hash": b }),
+    );
+    assert_request(
+        &GetAdditionsAndRemovals { header_hash: &a },
+        "get_additions_and_removals",
+        json!({ "header_hash": a }),
+    );
+    assert_request(
+        &GetInitialFreezePeriod {},
+        "get_initial_freeze_period",
+        json!({}),
+    );
+    assert_request(
+        &GetRecentSignagePointOrEos {
+            sp_hash: Some(&a),
+            challenge_hash: None,
+        },
+        "get_recent_signage_point_or_eos",
+        json!({ "sp_hash": a }),
+    );
+    assert_request(
+        &GetCoinRecordsByPuzzleHash {
+            puzzle_hash: &a,
+            include_spent_coins: true,
+            start_height: 1,
+            end_height: 100,
+        },
+        "get_coin_records_by_puzzle_hash",
+        json!({
+            "puzzle_hash": a,
+            "include_spent_coins": true,
+            "start_height": 1,
+            "end_height": 100,
+        }),
+    );
+    assert_request(
+        &GetCoinRecordsByPuzzleHashes {
+            puzzle_hashes: vec![&a, &b],
+            include_spent_coins: false,
+            start_height: 0,
+            end_height: 50,
+        },
+        "get_coin_records_by_puzzle_hashes",
+        json!({
+            "puzzle_hashes": [a, b],
+            "include_spent_coins": false,
+            "start_height": 0,
+            "end_height": 50,
+        }),
+    );
+    assert_request(
+        &GetCoinRecordByName { name: &a },
+        "get_coin_record_by_name",
+        json!({ "name": a }),
+    );
+    assert_request(
+        &GetCoinRecordsByParentIds {
+            parent_ids: vec![&a, &b],
+            include_spent_coins: true,
+            start_height: 3,
+            end_height: 9,
+        },
+        "get_coin_records_by_parent_ids",
+        json!({
+            "parent_ids": [a, b],
+            "include_spent_coins": true,
+            "start_height": 3,
+            "end_height": 9,
+        }),
+    );
+    let spend_bundle: SpendBundle = serde_json::from_value(json!({
+        "coin_spends": [],
+        "aggregated_signature": format!("0xc0{}", "00".repeat(95)),
+    }))
+    .unwrap();
+    assert_request(
+        &PushTx {
+            spend_bundle: &spend_bundle,
+        },
+        "push_tx",
+        json!({ "spend_bundle": spend_bundle }),
+    );
+    assert_request(
+        &GetPuzzleAndSolution {
+            coin_id: &a,
+            height: 42,
+        },
+        "get_puzzle_and_solution",
+        json!({ "coin_id": a, "height": 42 }),
+    );
+    assert_request(&GetAllMempoolTxIds {}, "get_all_mempool_tx_ids", json!({}));
+    assert_request(&GetAllMempoolItems {}, "get_all_mempool_items", json!({}));
+    assert_request(
+        &GetMempoolItemByTxId { tx_id: "0xabcd" },
+        "get_mempool_item_by_tx_id",
+        json!({ "tx_id": "0xabcd" }),
+    );
+}
+
+#[test]
+fn wallet_requests() {
+    assert_request(
+        &LogIn {
+            wallet_fingerprint: 123,
+        },
+        "log_in",
+        json!({ "wallet_fingerprint": 123 }),
+    );
+    assert_request(
+        &LogInAndSkip {
+            wallet_fingerprint: 123,
+        },
+        "log_in_and_skip",
+        json!({ "wallet_fingerprint": 123 }),
+    );
+    assert_request(&GetWallets {}, "get_wallets", json!({}));
+    assert_request(
+        &GetWalletBalance { wallet_id: 1 },
+        "get_wallet_balance",
+        json!({ "wallet_id": 1 }),
+    );
+    assert_request(&GetSyncStatus {}, "get_sync_status", json!({}));
+    assert_request(
+        &SendTransaction {
+            wallet_id: 1,
+            amount: 1000,
+            address: "xch1abc",
+            fee: 5,
+        },
+        "send_transaction",
+        json!({ "wallet_id": 1, "amount": 1000, "address": "xch1abc", "fee": 5 }),
+    );
+    assert_request(
+        &SendTransactionMulti {
+            wallet_id: 1,
+            additions: &[],
+            fee: 5,
+        },
+        "send_transaction_multi",
+        json!({ "wallet_id": 1, "additions": [], "fee": 5 }),
+    );
+    assert_request(
+        &GetTransaction {
+            wallet_id: 1,
+            transaction_id: "0xabcd",
+        },
+        "get_transaction",
+        json!({ "wallet_id": 1, "transaction_id": "0xabcd" }),
+    );
+    assert_request(
+        &CreateSignedTransaction {
+            wallet_id: 1,
+            additions: &[],
+            coins: &[],
+            fee: 5,
+        },
+        "create_signed_transaction",
+        json!({ "wallet_id": 1, "additions": [], "coins": [], "fee": 5 }),
+    );
+}
+
+#[test]
+fn farmer_requests() {
+    let a = hash(1);
+    assert_request(
+        &GetSignagePoint { sp_hash: &a },
+        "get_signage_point",
+        json!({ "sp_hash": a }),
+    );
+    assert_request(&GetSignagePoints {}, "get_signage_points", json!({}));
+    assert_request(
+        &GetRewardTargets {
+            search_for_private_key: true,
+            max_ph_to_search: Some(500),
+        },
+        "get_reward_targets",
+        json!({ "search_for_private_key": true, "max_ph_to_search": 500 }),
+    );
+    assert_request(
+        &GetRewardTargets {
+            search_for_private_key: false,
+            max_ph_to_search: None,
+        },
+        "get_reward_targets",
+        json!({ "search_for_private_key": false }),
+    );
+    assert_request(
+        &SetRewardTargets {
+            farmer_target: Some("xch1farmer"),
+            pool_target: None,
+        },
+        "set_reward_targets",
+        json!({ "farmer_target": "xch1farmer" }),
+    );
+    assert_request(&GetPoolState {}, "get_pool_state", json!({}));
+    assert_request(
+        &SetPayoutInstructions {
+            launcher_id: &a,
+            payout_instructions: "xch1payout",
+        },
+        "set_payout_instructions",
+        json!({ "launcher_id": a, "payout_instructions": "xch1payout" }),
+    );
+    assert_request(&GetHarvesters {}, "get_harvesters", json!({}));
+    assert_request(
+        &GetHarvestersSummary {},
+        "get_harvesters_summary",
+        json!({}),
+    );
+    let filter = [PlotFilter {
+        key: "filename".to_string(),
+        value: Some("k32".to_string()),
+    }];
+    assert_request(
+        &GetHarvesterPlotsValid {
+            node_id: &a,
+            page: 2,
+            page_size: 50,
+            filter: &filter,
+            sort_key: "size",
+            reverse: true,
+        },
+        "get_harvester_plots_valid",
+        json!({
+            "node_id": a,
+            "page": 2,
+            "page_size": 50,
+            "filter": [{ "key": "filename", "value": "k32" }],
+            "sort_key": "size",
+            "reverse": true,
+        }),
+    );
+    let names = ["plot-1".to_string()];
+    let page = json!({
+        "node_id": a,
+        "page": 0,
+        "page_size": 10,
+        "filter": ["plot-1"],
+        "reverse": false,
+    });
+    assert_request(
+        &GetHarvesterPlotsInvalid {
+            node_id: &a,
+            page: 0,
+            page_size: 10,
+            filter: &names,
+            reverse: false,
+        },
+        "get_harvester_plots_invalid",
+        page.clone(),
+    );
+    assert_request(
+        &GetHarvesterPlotsKeysMissing {
+            node_id: &a,
+            page: 0,
+            page_size: 10,
+            filter: &names,
+            reverse: false,
+        },
+        "get_harvester_plots_keys_missing",
+        page.clone(),
+    );
+    assert_request(
+        &GetHarvesterPlotsDuplicates {
+            node_id: &a,
+            page: 0,
+            page_size: 10,
+            filter: &names,
+            reverse: false,
+        },
+        "get_harvester_plots_duplicates",
+        page,
+    );
+    assert_request(
+        &GetPoolLoginLink { launcher_id: &a },
+        "get_pool_login_link",
+        json!({ "launcher_id": a }),
+    );
+}
+
+#[test]
+fn harvester_requests() {
+    assert_request(&GetPlots {}, "get_plots", json!({}));
+    assert_request(&RefreshPlots {}, "refresh_plots", json!({}));
+    assert_request(
+        &DeletePlot {
+            filename: "/plots/a.plot",
+        },
+        "delete_plot",
+        json!({ "filename": "/plots/a.plot" }),
+    );
+    assert_request(
+        &AddPlotDirectory { dirname: "/plots" },
+        "add_plot_directory",
+        json!({ "dirname": "/plots" }),
+    );
+    assert_request(&GetPlotDirectories {}, "get_plot_directories", json!({}));
+    assert_request(
+        &RemovePlotDirectory { dirname: "/plots" },
+        "remove_plot_directory",
+        json!({ "dirname": "/plots" }),
+    );
+    assert_request(&GetHarvesterConfig {}, "get_harvester_config", json!({}));
+    let config = HarvesterConfig {
+        use_gpu_harvesting: Some(true),
+        gpu_index: Some(1),
+        ..HarvesterConfig::default()
+    };
+    assert_request(
+        &UpdateHarvesterConfig { config: &config },
+        "update_harvester_config",
+        json!({ "use_gpu_harvesting": true, "gpu_index": 1 }),
+    );
+}
+
+#[test]
+fn data_layer_requests() {
+    let (a, b) = (hash(1), hash(2));
+    assert_request(
+        &CreateDataStore { fee: None },
+        "create_data_store",
+        json!({}),
+    );
+    assert_request(
+        &CreateDataStore { fee: Some(5) },
+        "create_data_store",
+        json!({ "fee": 5 }),
+    );
+    assert_request(&GetOwnedStores {}, "get_owned_stores", json!({}));
+    let changelist = [
+        Change::Insert {
+            key: HexBytes::from("k"),
+            value: HexBytes::from("v"),
+        },
+        Change::Delete {
+            key: HexBytes::from("k"),
+        },
+    ];
+    assert_request(
+        &BatchUpdate {
+            id: &a,
+            changelist: &changelist,
+            fee: Some(1),
+        },
+        "batch_update",
+        json!({
+            "id": a,
+            "changelist": [
+                { "action": "insert", "key": "0x6b", "value": "0x76" },
+                { "action": "delete", "key": "0x6b" },
+            ],
+            "fee": 1,
+        }),
+    );
+    assert_request(
+        &GetKeysValues {
+            id: &a,
+            root_hash: None,
+        },
+        "get_keys_values",
+        json!({ "id": a }),
+    );
+    let key = HexBytes::from("k");
+    assert_request(
+        &GetValue {
+            id: &a,
+            key: &key,
+            root_hash: Some(&b),
+        },
+        "get_value",
+        json!({ "id": a, "key": "0x6b", "root_hash": b }),
+    );
+    assert_request(&GetRoot { id: &a }, "get_root", json!({ "id": a }));
+    assert_request(
+        &GetRootHistory { id: &a },
+        "get_root_history",
+        json!({ "id": a }),
+    );
+    assert_request(
+        &GetKvDiff {
+            id: &a,
+            hash_1: &a,
+            hash_2: &b,
+        },
+        "get_kv_diff",
+        json!({ "id": a, "hash_1": a, "hash_2": b }),
+    );
+    let urls = ["http://mirror:8575".to_string()];
+    assert_request(
+        &Subscribe {
+            id: &a,
+            urls: &urls,
+        },
+        "subscribe",
+        json!({ "id": a, "urls": urls }),
+    );
+    assert_request(
+        &Unsubscribe {
+            id: &a,
+            retain: true,
+        },
+        "unsubscribe",
+        json!({ "id": a, "retain": true }),
+    );
+    assert_request(
+        &AddMirror {
+            id: &a,
+            urls: &urls,
+            amount: 100,
+            fee: None,
+        },
+        "add_mirror",
+        json!({ "id": a, "urls": urls, "amount": 100 }),
+    );
+    assert_request(&GetMirrors { id: &a }, "get_mirrors", json!({ "id": a }));
+    assert_request(
+        &data_layer::GetSyncStatus { id: &a },
+        "get_sync_status",
+        json!({ "id": a }),
+    );
+}
+
+#[test]
+fn simulator_requests() {
+    assert_request(
+        &FarmBlock {
+            address: "txch1abc",
+            blocks: 2,
+            guarantee_tx_block: true,
+        },
+        "farm_block",
+        json!({ "address": "txch1abc", "blocks": 2, "guarantee_tx_block": true }),
+    );
+    assert_request(
+        &SetAutoFarming { auto_farm: true },
+        "set_auto_farming",
+        json!({ "auto_farm": true }),
+    );
+    assert_request(&GetAutoFarming {}, "get_auto_farming", json!({}));
+    assert_request(&GetFarmingPh {}, "get_farming_ph", json!({}));
+    assert_request(
+        &RevertBlocks {
+            num_of_blocks: 3,
+            delete_all_blocks: false,
+        },
+        "revert_blocks",
+        json!({ "num_of_blocks": 3, "delete_all_blocks": false }),
+    );
+    assert_request(
+        &ReorgBlocks {
+            num_of_blocks_to_rev: 3,
+            num_of_new_blocks: 4,
+            revert_all_blocks: false,
+        },
+        "reorg_blocks",
+        json!({ "num_of_blocks_to_rev": 3, "num_of_new_blocks": 4, "revert_all_blocks": false }),
+    );
+    assert_request(
+        &GetAllCoins {
+            include_spent_coins: true,
+        },
+        "get_all_coins",
+        json!({ "include_spent_coins": true }),
+    );
+    assert_request(&GetAllPuzzleHashes {}, "get_all_puzzle_hashes", json!({}));
+}
+
+#[test]
+fn crawler_requests() {
+    assert_request(&GetPeerCounts {}, "get_peer_counts", json!({}));
+    assert_request(
+        &GetIpsAfterTimestamp {
+            after: 1_700_000_000,
+            offset: 0,
+            limit: 100,
+        },
+        "get_ips_after_timestamp",
+        json!({ "after": 1_700_000_000u64, "offset": 0, "limit": 100 }),
+    );
+}
+
+#[tokio::test]
+async fn get_blocks_sends_exclude_header_hash_as_bool() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond("get_blocks", json!({ "success": true, "blocks": [] }));
+    let client = FullnodeClient::from_transport(transport.clone());
+    client.get_blocks(1, 3, true).await.unwrap();
+    assert_eq!(
+        transport.requests_to("get_blocks"),
+        vec![json!({ "start": 1, "end": 3, "exclude_header_hash": true })]
+    );
+}
+
+#[tokio::test]
+async fn get_coin_records_by_parent_ids_uses_its_route() {
+    let transport = Arc::new(MockTransport::new());
+    transport.respond(
+        "get_coin_records_by_parent_ids",
+        json!({ "success": true, "coin_records": [] }),
+    );
+    let client = FullnodeClient::from_transport(transport.clone());
+    let parent = hash(3);
+    client
+        .get_coin_records_by_parent_ids(vec![&parent], false, 0, 10)
+        .await
+        .unwrap();
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].endpoint, "get_coin_records_by_parent_ids");
+    assert_eq!(requests[0].body["parent_ids"], json!([parent]));
+}